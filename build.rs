@@ -0,0 +1,44 @@
+//! Generates the registry of solution modules used by the criterion benchmarks in `src/bench`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let is_day = path.extension()? == "rs"
+                        && stem.len() == 2
+                        && stem.chars().all(|c| c.is_ascii_digit());
+                    is_day.then_some(stem)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut modules = String::new();
+    let mut calls = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        modules.push_str(&format!(
+            "#[path = {:?}]\n#[allow(warnings, clippy::all, clippy::pedantic)]\nmod day_{day};\n",
+            path.display().to_string()
+        ));
+        let number = day.trim_start_matches('0');
+        calls.push_str(&format!("    bench_day!(c, {number}, day_{day});\n"));
+    }
+
+    let generated = format!("{modules}\nfn bench_days(c: &mut Criterion) {{\n{calls}}}\n");
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("bench_days.rs");
+    fs::write(out_path, generated).unwrap();
+}
@@ -1,3 +1,58 @@
-pub fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("", |b| b.iter(|| fibonacci(black_box(20))));
+//! Criterion benchmarks for every scaffolded day, grouped by day number.
+//! Run all of them with `cargo bench` or a single day with e.g. `cargo bench -- 16`.
+#![cfg_attr(feature = "dhat-heap", allow(unused))]
+use advent_of_code::memo::clear_caches;
+use advent_of_code::template::{has_file, read_file, Day};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Registers the benchmarks of a solution module, if its real input is present in plain text or encrypted.
+/// Memoization caches are cleared before every iteration, so that all of them start cold.
+macro_rules! bench_day {
+    ($c:expr, $day:expr, $module:ident) => {{
+        let day = advent_of_code::day!($day);
+        if has_input(day) {
+            let input = read_file("inputs", day);
+            let mut group = $c.benchmark_group(day.to_string());
+            if let Some(parser) = $module::PARSER {
+                group.bench_function("parse", |b| b.iter(|| parser(black_box(&input))));
+            }
+            group.bench_function("part_one", |b| {
+                b.iter(|| {
+                    clear_caches();
+                    $module::part_one(black_box(&input))
+                })
+            });
+            group.bench_function("part_two", |b| {
+                b.iter(|| {
+                    clear_caches();
+                    $module::part_two(black_box(&input))
+                })
+            });
+            group.finish();
+        }
+    }};
 }
+
+// every solution module defines the DHAT allocator under `dhat-heap`, and a crate can only have one.
+#[cfg(not(feature = "dhat-heap"))]
+include!(concat!(env!("OUT_DIR"), "/bench_days.rs"));
+
+#[cfg(feature = "dhat-heap")]
+fn bench_days(_: &mut Criterion) {
+    eprintln!(
+        "Skipping all days: criterion benchmarks are not available with the `dhat-heap` feature."
+    );
+}
+
+fn has_input(day: Day) -> bool {
+    let exists = has_file("inputs", day);
+    if !exists {
+        eprintln!(
+            "Skipping day {day}: no input at \"data/inputs/{day}.txt\" or its encrypted copy."
+        );
+    }
+    exists
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
+use advent_of_code::parse::unsigned;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::digit1,
     combinator::map_res,
     sequence::{delimited, separated_pair},
     IResult,
@@ -77,17 +77,13 @@ fn mul(input: &str) -> IResult<&str, Instruction> {
     map_res(
         delimited(
             tag("mul("),
-            separated_pair(number, tag(","), number),
+            separated_pair(unsigned, tag(","), unsigned),
             tag(")"),
         ),
-        |(a, b)| Ok::<Instruction, nom::error::Error<&str>>(Instruction::Mul(a * b)),
+        |(a, b): (u32, u32)| Ok::<Instruction, nom::error::Error<&str>>(Instruction::Mul(a * b)),
     )(input)
 }
 
-fn number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, |s: &str| s.parse::<u32>())(input)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
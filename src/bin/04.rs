@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let vec = parse(input);
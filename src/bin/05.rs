@@ -1,18 +1,19 @@
 use std::{cmp::Ordering, collections::HashMap, usize::MAX};
 
+use advent_of_code::parse::{lines_of, parse_or_report, unsigned};
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, line_ending, newline},
+    character::complete::line_ending,
     combinator::map_res,
-    multi::{many1, separated_list1},
-    sequence::{separated_pair, terminated},
+    multi::separated_list1,
+    sequence::{pair, separated_pair},
     IResult,
 };
 
 advent_of_code::solution!(5);
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let input_values = parse_input(input).unwrap().1;
+    let input_values = parse_or_report(parse_input, input)?;
     Some(
         input_values
             .issues
@@ -24,7 +25,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let input_values = parse_input(input).unwrap().1;
+    let input_values = parse_or_report(parse_input, input)?;
     Some(
         input_values
             .issues
@@ -69,7 +70,11 @@ pub fn order(a: u32, b: u32, rules: &Vec<(u32, u32)>) -> Ordering {
 
 pub fn parse_input(input: &str) -> IResult<&str, Input> {
     let value = map_res(
-        separated_pair(many1(update), line_ending, many1(issues)),
+        separated_pair(
+            lines_of(update),
+            pair(line_ending, line_ending),
+            lines_of(issues),
+        ),
         |f| {
             Ok::<_, nom::error::Error<&str>>(Input {
                 update: f.0,
@@ -81,15 +86,11 @@ pub fn parse_input(input: &str) -> IResult<&str, Input> {
 }
 
 pub fn issues(input: &str) -> IResult<&str, Vec<u32>> {
-    terminated(separated_list1(tag(","), number), line_ending)(input)
+    separated_list1(tag(","), unsigned)(input)
 }
 
 pub fn update(input: &str) -> IResult<&str, (u32, u32)> {
-    terminated(separated_pair(number, tag("|"), number), line_ending)(input)
-}
-
-fn number(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, |s: &str| s.parse::<u32>())(input)
+    separated_pair(unsigned, tag("|"), unsigned)(input)
 }
 
 #[derive(Clone)]
@@ -111,6 +112,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(76));
+        assert_eq!(result, Some(123));
     }
 }
@@ -1,19 +1,23 @@
-use std::collections::HashSet;
-
+use std::iter;
+
+use advent_of_code::{
+    collections::BitGrid,
+    cycles::brent,
+    grid::Grid,
+    point::{Direction, Point},
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 advent_of_code::solution!(6);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let game = parse(input);
-    let (_, pos) = compute_game(game);
-    return Some(pos.len());
+    return Some(game.distinct_pos().len());
 }
 
 pub fn part_two(input: &str) -> Option<i32> {
     let game = parse(input);
-    let original_game = game.clone();
-    let (_, pos) = compute_game(original_game);
+    let pos: Vec<Point> = game.distinct_pos().iter().collect();
 
     let count = pos
         .par_iter()
@@ -23,126 +27,58 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(count)
 }
 
-fn test_obstacle(mut game: BoardState, obstacle_pos: (usize, usize)) -> i32 {
-    game.extra_obstacle = Some((obstacle_pos.0 as i32, obstacle_pos.1 as i32));
-    match compute_game(game) {
-        (StepResult::LOOP, _) => return 1,
-        _ => return 0,
+fn test_obstacle(mut game: BoardState, obstacle_pos: Point) -> i32 {
+    game.extra_obstacle = Some(obstacle_pos);
+    match brent(game.guard, |guard| game.step_board(guard)) {
+        Some(_) => return 1,
+        None => return 0,
     }
 }
 
-fn compute_game(mut new_game: BoardState) -> (StepResult, HashSet<(usize, usize)>) {
-    loop {
-        let step = new_game.step_board();
-        match step {
-            StepResult::CONTINUE => {
-                continue;
-            }
-            _ => {
-                return (step, new_game.distinct_pos());
-            }
-        }
-    }
-}
+/// The position of the guard and the direction it faces.
+type Guard = (Point, Direction);
 
 #[derive(Clone)]
 struct BoardState {
-    board: Vec<Vec<bool>>,
-    board_dim: (usize, usize),
-    current_pos: (usize, usize),
-    current_dir: (i32, i32),
-    visited_pos: HashSet<((usize, usize), (i32, i32))>,
-    extra_obstacle: Option<(i32, i32)>,
-}
-
-enum StepResult {
-    LOOP,
-    EXIT,
-    CONTINUE,
+    board: Grid<bool>,
+    guard: Guard,
+    extra_obstacle: Option<Point>,
 }
 
 impl BoardState {
-    fn distinct_pos(&mut self) -> HashSet<(usize, usize)> {
-        self.visited_pos.iter().map(|c| c.0).collect()
+    /// The positions the guard visits until it leaves the board.
+    fn distinct_pos(&self) -> BitGrid {
+        let mut visited = BitGrid::for_grid(&self.board);
+        iter::successors(Some(self.guard), |guard| self.step_board(guard)).for_each(|(pos, _)| {
+            visited.insert(pos);
+        });
+        visited
     }
 
-    fn step_board(&mut self) -> StepResult {
-        if self
-            .visited_pos
-            .contains(&(self.current_pos, self.current_dir))
-        {
-            return StepResult::LOOP;
-        }
-
-        self.visited_pos
-            .insert((self.current_pos, self.current_dir));
-        let next_pos = (
-            (self.current_pos.0 as i32 + self.current_dir.0),
-            (self.current_pos.1 as i32 + self.current_dir.1),
-        );
-
-        if next_pos.0 >= self.board_dim.0 as i32
-            || next_pos.1 >= self.board_dim.1 as i32
-            || next_pos.0 < 0
-            || next_pos.1 < 0
-        {
-            return StepResult::EXIT;
-        }
+    /// Moves or turns the guard, returning `None` once it leaves the board.
+    fn step_board(&self, &(current_pos, current_dir): &Guard) -> Option<Guard> {
+        let next_pos = current_pos + current_dir.vector();
+        let free = *self.board.get(next_pos)?;
 
-        if !self
-            .board
-            .get(next_pos.1 as usize)
-            .unwrap()
-            .get(next_pos.0 as usize)
-            .unwrap()
-            || self.extra_obstacle.is_some_and(|c| c == next_pos)
-        {
-            let next_dir = match self.current_dir {
-                (0, 1) => (-1, 0),
-                (0, -1) => (1, 0),
-                (-1, 0) => (0, -1),
-                (1, 0) => (0, 1),
-                _ => panic!("Impossible dir"),
-            };
-            self.current_dir = next_dir;
-            return StepResult::CONTINUE;
+        if !free || self.extra_obstacle.is_some_and(|c| c == next_pos) {
+            return Some((current_pos, current_dir.turn_right()));
         }
 
-        self.current_pos = (next_pos.0 as usize, next_pos.1 as usize);
-        StepResult::CONTINUE
+        Some((next_pos, current_dir))
     }
 }
 
 fn parse(input: &str) -> BoardState {
-    let board: Vec<Vec<bool>> = input
-        .lines()
-        .map(|f| f.chars().map(|c| c != '#').collect())
-        .collect();
-    let guard = input
-        .lines()
-        .enumerate()
-        .filter_map(|l| {
-            l.1.chars()
-                .enumerate()
-                .filter_map(|c| match c.1 {
-                    '^' => Some(((c.0, l.0), (0, -1))),
-                    '<' => Some(((c.0, l.0), (-1, 0))),
-                    'v' => Some(((c.0, l.0), (0, 1))),
-                    '>' => Some(((c.0, l.0), (1, 0))),
-                    _ => None,
-                })
-                .next()
-        })
-        .next()
+    let board = Grid::parse(input, |c| c != '#');
+    let guard = Grid::parse(input, |c| Direction::try_from(c).ok());
+    let (guard_pos, guard_dir) = guard
+        .iter()
+        .find_map(|(pos, dir)| Some((pos, (*dir)?)))
         .unwrap();
-    let size = (board.get(0).unwrap().len(), board.len());
 
     BoardState {
         board: board,
-        board_dim: size,
-        current_pos: guard.0,
-        current_dir: guard.1,
-        visited_pos: HashSet::new(),
+        guard: (guard_pos, guard_dir),
         extra_obstacle: None,
     }
 }
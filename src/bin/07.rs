@@ -1,17 +1,14 @@
 use std::usize;
 
+use advent_of_code::parse::{lines_of, parse_or_report, unsigned};
 use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, line_ending, newline},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
+    bytes::complete::tag, combinator::map_res, multi::separated_list1, sequence::separated_pair,
     IResult,
 };
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse = parse);
 
 pub fn part_one(input: &str) -> Option<u64> {
     compute_sum(input, vec![Operators::PLUS, Operators::MUL])
@@ -25,7 +22,7 @@ pub fn part_two(input: &str) -> Option<u64> {
 }
 
 fn compute_sum(input: &str, possible_ops: Vec<Operators>) -> Option<u64> {
-    let input = parse(input).unwrap().1;
+    let input = parse_or_report(parse, input)?;
     Some(
         input
             .par_iter()
@@ -101,13 +98,9 @@ impl Data {
     }
 }
 
-fn number(input: &str) -> IResult<&str, u64> {
-    map_res(digit1, |s: &str| s.parse::<u64>())(input)
-}
-
 pub fn parse_line(input: &str) -> IResult<&str, Data> {
     map_res(
-        separated_pair(number, tag(": "), separated_list1(tag(" "), number)),
+        separated_pair(unsigned, tag(": "), separated_list1(tag(" "), unsigned)),
         |(result, inputs)| -> Result<Data, nom::error::Error<&str>> {
             Ok(Data {
                 result: result,
@@ -118,7 +111,7 @@ pub fn parse_line(input: &str) -> IResult<&str, Data> {
 }
 
 pub fn parse(input: &str) -> IResult<&str, Vec<Data>> {
-    separated_list1(line_ending, parse_line)(input)
+    lines_of(parse_line)(input)
 }
 
 #[cfg(test)]
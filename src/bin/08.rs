@@ -6,7 +6,7 @@ use std::{
 
 use itertools::Itertools;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse = parse);
 
 pub fn part_one(input: &str) -> Option<usize> {
     compute(input, true)
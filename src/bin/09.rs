@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::ops::Range;
 
-use itertools::Itertools;
+use advent_of_code::intervals::SpanAllocator;
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, parse = parse);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut disk = parse(input);
@@ -34,46 +34,25 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 pub fn part_two(input: &str) -> Option<usize> {
     let (mut files, mut freespace) = parse_as_block(input);
-    files.clone().keys().sorted().rev().for_each(|f| {
-        let current_slot = files.get(f).unwrap();
-        let possible_pos = find_possible_pos(&freespace, current_slot.length);
-        if possible_pos.is_some() && possible_pos.unwrap().1.begin < current_slot.begin {
-            let (idx, new_pos) = possible_pos.unwrap();
-            let mut pos_to_insert = new_pos.clone();
-            let mut remaining_space: Option<DiskSlot> = None;
-            if new_pos.length > current_slot.length {
-                pos_to_insert = DiskSlot {
-                    begin: new_pos.begin,
-                    length: current_slot.length,
-                };
-                remaining_space = Some(DiskSlot {
-                    begin: new_pos.begin + current_slot.length,
-                    length: new_pos.length - current_slot.length,
-                });
-            }
-            files.entry(*f).and_modify(|v| *v = pos_to_insert);
-            freespace.remove(idx);
-            if remaining_space.is_some() {
-                freespace.insert(idx, remaining_space.unwrap());
-            }
-        }
-    });
-    Some(check_sum_block(files))
-}
-
-fn find_possible_pos(freespace: &Vec<DiskSlot>, size: u32) -> Option<(usize, &DiskSlot)> {
-    freespace
-        .iter()
-        .enumerate()
-        .find(|(_, slot)| slot.length >= size)
+    for file in files.iter_mut().rev().filter(|f| f.start < f.end) {
+        let Some(start) = freespace
+            .first_fit(file.len())
+            .filter(|&start| start < file.start)
+        else {
+            continue;
+        };
+        let moved = start..start + file.len();
+        freespace.take(moved.clone());
+        freespace.release(std::mem::replace(file, moved));
+    }
+    Some(check_sum_block(&files))
 }
 
-fn check_sum_block(files: HashMap<usize, DiskSlot>) -> usize {
+fn check_sum_block(files: &[Range<usize>]) -> usize {
     files
         .iter()
-        .map(|(id, slot)| {
-            slot.length as usize * id * (2 * slot.begin + slot.length - 1) as usize / 2
-        })
+        .enumerate()
+        .map(|(id, slot)| id * slot.clone().sum::<usize>())
         .sum()
 }
 
@@ -84,30 +63,19 @@ pub fn check_sum(disk: Vec<Option<usize>>) -> usize {
         .sum()
 }
 
-fn parse_as_block(input: &str) -> (HashMap<usize, DiskSlot>, Vec<DiskSlot>) {
-    let mut files = HashMap::new();
-    let mut freespace: Vec<DiskSlot> = Vec::new();
+/// Returns the blocks of every file, by id, and the free space between them.
+fn parse_as_block(input: &str) -> (Vec<Range<usize>>, SpanAllocator) {
+    let mut files = Vec::new();
+    let mut freespace = SpanAllocator::new();
     let mut current_idx = 0;
-    input.chars().enumerate().for_each(|(idx, c)| {
-        let count = c.to_string().parse::<u32>().unwrap();
+    input.trim().chars().enumerate().for_each(|(idx, c)| {
+        let count = c.to_digit(10).unwrap() as usize;
+        let slot = current_idx..current_idx + count;
         match idx % 2 {
-            0 => {
-                files.insert(
-                    idx / 2,
-                    DiskSlot {
-                        begin: current_idx,
-                        length: count,
-                    },
-                );
-            }
-            _ => {
-                freespace.push(DiskSlot {
-                    begin: current_idx,
-                    length: count,
-                });
-            }
+            0 => files.push(slot),
+            _ => freespace.release(slot),
         }
-        current_idx = current_idx + count;
+        current_idx += count;
     });
     (files, freespace)
 }
@@ -128,12 +96,6 @@ pub fn parse(input: &str) -> Vec<Option<usize>> {
     result
 }
 
-#[derive(Clone)]
-struct DiskSlot {
-    begin: u32,
-    length: u32,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse = parse);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let map = parse(input);
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use advent_of_code::math::split_digits;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse = parse);
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut init_stones = parse(input);
@@ -11,67 +11,28 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut map: HashMap<u64, u64> =
+    Some(
         parse(input)
-            .iter()
-            .fold(HashMap::new(), |mut map: HashMap<u64, u64>, c| {
-                *map.entry(*c).or_default() += 1;
-                map
-            });
-    for _ in 0..75 {
-        map = step_map(map);
-    }
-    let mut memo: HashMap<(u64, u32), u64> = HashMap::new();
-    let init_stones = parse(input);
-    let mut sum = 0;
-    for stone in init_stones {
-        sum += with_memo(stone, 75, &mut memo);
-    }
-    println!("{}", sum);
-    Some(map.values().sum())
-}
-
-pub fn with_memo(value: u64, step: u32, mut memo: &mut HashMap<(u64, u32), u64>) -> u64 {
-    if step == 0 {
-        return 1;
-    }
-    if memo.get(&(value, step)).is_some() {
-        return *memo.get(&(value, step)).unwrap();
-    }
-    if value == 0 {
-        return with_memo(1, step - 1, memo);
-    }
-    let digit_count = (value.ilog10() + 1) as usize;
-    let result;
-    if digit_count % 2 == 0 {
-        let k1 = value / 10u64.pow((digit_count / 2) as u32);
-        let k2 = value % 10u64.pow((digit_count / 2) as u32);
-        result = with_memo(k1, step - 1, &mut memo) + with_memo(k2, step - 1, &mut memo);
-    } else {
-        result = with_memo(value * 2024, step - 1, &mut memo)
-    }
-    memo.insert((value, step), result);
-    result
+            .into_iter()
+            .map(|stone| with_memo(stone, 75))
+            .sum(),
+    )
 }
 
-pub fn step_map(map: HashMap<u64, u64>) -> HashMap<u64, u64> {
-    let mut next_map = HashMap::new();
-    map.iter().for_each(|(k, c)| {
-        if *k == 0 {
-            *next_map.entry(1).or_insert(0) += c;
+advent_of_code::memoize! {
+    pub fn with_memo(value: u64, step: u32) -> u64 {
+        if step == 0 {
+            return 1;
+        }
+        if value == 0 {
+            return with_memo(1, step - 1);
+        }
+        if let Some((k1, k2)) = split_digits(value) {
+            with_memo(k1, step - 1) + with_memo(k2, step - 1)
         } else {
-            let digit_count = (k.ilog10() + 1) as usize;
-            if digit_count % 2 == 0 {
-                let k1 = k / 10u64.pow((digit_count / 2) as u32);
-                let k2 = k % 10u64.pow((digit_count / 2) as u32);
-                *next_map.entry(k1).or_insert(0) += c;
-                *next_map.entry(k2).or_insert(0) += c;
-            } else {
-                *next_map.entry(k * 2024).or_insert(0) += c;
-            }
+            with_memo(value * 2024, step - 1)
         }
-    });
-    next_map
+    }
 }
 
 pub fn step(state: &mut Vec<u64>) -> Vec<u64> {
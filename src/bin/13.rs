@@ -1,21 +1,20 @@
 use std::{cmp::min, usize::MAX};
 
+use advent_of_code::math::solve_2x2;
+use advent_of_code::parse::{parse_or_report, unsigned};
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, newline},
-    combinator::{map_res, opt},
-    multi::many1,
-    sequence::{preceded, separated_pair, terminated, tuple},
+    character::complete::newline,
+    combinator::opt,
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, parse = parse);
 
 pub fn part_one(input: &str) -> Option<isize> {
-    let (rem, mut machine) =
-        many1(terminated(parse_claw, tuple((newline, newline))))(input).unwrap();
-    let (_, last_machine) = parse_claw(rem).unwrap();
-    machine.push(last_machine);
+    let machine = parse_or_report(parse, input)?;
     let values: Vec<Option<isize>> = machine.iter().map(|m| solve_machine(m, 0)).collect();
     Some(
         values
@@ -27,10 +26,7 @@ pub fn part_one(input: &str) -> Option<isize> {
 }
 
 pub fn part_two(input: &str) -> Option<isize> {
-    let (rem, mut machine) =
-        many1(terminated(parse_claw, tuple((newline, newline))))(input).unwrap();
-    let (_, last_machine) = parse_claw(rem).unwrap();
-    machine.push(last_machine);
+    let machine = parse_or_report(parse, input)?;
     let values: Vec<Option<isize>> = machine
         .iter()
         .map(|m| solve_machine(m, 10000000000000))
@@ -44,25 +40,27 @@ pub fn part_two(input: &str) -> Option<isize> {
     )
 }
 fn solve_machine(machine: &ClawMachine, offset: isize) -> Option<isize> {
-    let prize = (machine.prize.0 + offset, machine.prize.1 + offset);
-    let det = machine.a.0 * machine.b.1 - machine.a.1 * machine.b.0;
-    let a = (prize.0 * machine.b.1 - prize.1 * machine.b.0) / det;
-    let b = (machine.a.0 * prize.1 - machine.a.1 * prize.0) / det;
-    if (
-        machine.a.0 * a + machine.b.0 * b,
-        machine.a.1 * a + machine.b.1 * b,
-    ) == (prize.0, prize.1)
-    {
-        Some(a * 3 + b)
-    } else {
-        None
-    }
+    let [a, b] = solve_2x2(
+        [
+            [machine.a.0 as i64, machine.b.0 as i64],
+            [machine.a.1 as i64, machine.b.1 as i64],
+        ],
+        [
+            (machine.prize.0 + offset) as i64,
+            (machine.prize.1 + offset) as i64,
+        ],
+    )?;
+    Some((a * 3 + b) as isize)
+}
+
+pub fn parse(input: &str) -> IResult<&str, Vec<ClawMachine>> {
+    separated_list1(pair(newline, newline), parse_claw)(input)
 }
 
 pub fn parse_claw(input: &str) -> IResult<&str, ClawMachine> {
-    let (input, a) = parse_button("A", input).unwrap();
-    let (input, b) = parse_button("B", input).unwrap();
-    let (rem, prize) = parse_prize(input).unwrap();
+    let (input, a) = parse_button("A", input)?;
+    let (input, b) = parse_button("B", input)?;
+    let (rem, prize) = parse_prize(input)?;
     return Ok((
         rem,
         ClawMachine {
@@ -76,7 +74,7 @@ pub fn parse_claw(input: &str) -> IResult<&str, ClawMachine> {
 pub fn parse_prize(input: &str) -> IResult<&str, (isize, isize)> {
     preceded(
         tag("Prize: X="),
-        separated_pair(number, tag(", Y="), number),
+        separated_pair(unsigned, tag(", Y="), unsigned),
     )(input)
 }
 
@@ -91,15 +89,11 @@ pub fn parse_button<'a>(name: &str, input: &'a str) -> IResult<&'a str, (isize,
 }
 
 pub fn parse_x(input: &str) -> IResult<&str, isize> {
-    preceded(tuple((tag("X"), tag("+"))), number)(input)
+    preceded(tuple((tag("X"), tag("+"))), unsigned)(input)
 }
 
 pub fn parse_y(input: &str) -> IResult<&str, isize> {
-    preceded(tuple((tag("Y"), tag("+"))), number)(input)
-}
-
-pub fn number(input: &str) -> IResult<&str, isize> {
-    map_res(digit1, |s: &str| s.parse::<isize>())(input)
+    preceded(tuple((tag("Y"), tag("+"))), unsigned)(input)
 }
 
 pub struct ClawMachine {
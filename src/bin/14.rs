@@ -1,76 +1,71 @@
-use std::{
-    collections::HashMap,
-    io::{self, Read},
-};
-
-use itertools::Itertools;
+use advent_of_code::math::crt;
+use advent_of_code::parse::{coordinates, lines_of, parse_or_report};
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, newline, space1},
-    combinator::{opt, recognize},
-    multi::many0,
-    sequence::{preceded, separated_pair, terminated},
+    character::complete::space1,
+    sequence::{preceded, separated_pair},
     IResult,
 };
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, parse = parse);
+
+const FIELD_SIZE: (usize, usize) = (101, 103);
+const EXAMPLE_FIELD_SIZE: (usize, usize) = (11, 7);
 
 pub fn part_one(input: &str) -> Option<isize> {
-    let mut robots = parse(input).unwrap().1;
-    let field_size = (101, 103);
-    (0..200000).for_each(|i| {
+    let mut robots = parse_or_report(parse, input)?;
+    let field_size = field_size(&robots);
+    for _ in 0..100 {
         robots.iter_mut().for_each(|r| step(r, field_size));
-        let score = score(&robots, field_size);
-        let mean_variation = mean_variation(&robots);
-        if (mean_variation.0 < 30 && mean_variation.1 < 30) {
-            println!("{}", display(&robots, field_size));
-            println!(
-                "Map {}, score {}, mean_x {}, mean_y {}",
-                i + 1,
-                score,
-                mean_variation.0,
-                mean_variation.1
-            );
-            pause();
-        }
-    });
+    }
     Some(score(&robots, field_size))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<i64> {
+    let robots = parse_or_report(parse, input)?;
+    let field_size = field_size(&robots);
+    // the example is too small to draw the easter egg.
+    if field_size != FIELD_SIZE {
+        return None;
+    }
+    // both coordinates move independently and repeat after the width and height of the field.
+    // the picture appears when the robots cluster on both axes, which is a CRT problem.
+    let x_time = most_clustered(&robots, field_size.0, |r| (r.pos.0, r.velocity.0));
+    let y_time = most_clustered(&robots, field_size.1, |r| (r.pos.1, r.velocity.1));
+    crt([(x_time, field_size.0 as i64), (y_time, field_size.1 as i64)]).map(|(time, _)| time)
 }
 
-pub fn mean_variation(robots: &Vec<Robot>) -> (usize, usize) {
-    let sum = robots
+/// The example uses a smaller field than the real input.
+pub fn field_size(robots: &[Robot]) -> (usize, usize) {
+    if robots
         .iter()
-        .tuple_windows()
-        .map(|(r1, r2)| (r1.pos.0.abs_diff(r2.pos.0), r1.pos.1.abs_diff(r2.pos.1)))
-        .fold((0, 0), |(a, b), (x, y)| (a + x, b + y));
-    return (sum.0 / robots.len(), sum.1 / robots.len());
-}
-
-fn pause() {
-    let mut stdin = io::stdin();
-
-    // Read a single byte and discard
-    let _ = stdin.read(&mut [0u8]).unwrap();
+        .all(|r| r.pos.0 < EXAMPLE_FIELD_SIZE.0 && r.pos.1 < EXAMPLE_FIELD_SIZE.1)
+    {
+        EXAMPLE_FIELD_SIZE
+    } else {
+        FIELD_SIZE
+    }
 }
 
-pub fn display(robots: &Vec<Robot>, field_size: (usize, usize)) -> String {
-    let mut map_vis = String::new();
-    let map: HashMap<(usize, usize), usize> = robots.iter().map(|r| r.pos).counts();
-    for i in 0..field_size.1 {
-        for j in 0..field_size.0 {
-            if map.contains_key(&(j, i)) {
-                map_vis.push_str(map.get(&(j, i)).unwrap().to_string().as_str());
-            } else {
-                map_vis.push_str(".");
-            }
-        }
-        map_vis.push('\n');
-    }
-    map_vis
+/// Returns the time in `0..bound` at which the positions on one axis have the smallest variance.
+pub fn most_clustered(
+    robots: &[Robot],
+    bound: usize,
+    axis: impl Fn(&Robot) -> (usize, isize),
+) -> i64 {
+    let bound = bound as isize;
+    let time = (0..bound).min_by_key(|&time| {
+        let positions: Vec<isize> = robots
+            .iter()
+            .map(|robot| {
+                let (pos, velocity) = axis(robot);
+                (pos as isize + velocity * time).rem_euclid(bound)
+            })
+            .collect();
+        let mean = positions.iter().sum::<isize>() / positions.len().max(1) as isize;
+        positions.iter().map(|p| (p - mean).pow(2)).sum::<isize>()
+    });
+    time.unwrap_or_default() as i64
 }
 
 pub fn score(robots: &Vec<Robot>, field_size: (usize, usize)) -> isize {
@@ -115,12 +110,11 @@ pub fn wrap(val: usize, step: isize, bound: usize) -> usize {
 }
 
 pub fn parse(input: &str) -> IResult<&str, Vec<Robot>> {
-    many0(robot)(input)
+    lines_of(robot)(input)
 }
 
 pub fn robot(input: &str) -> IResult<&str, Robot> {
-    let (rem, (p, v)) =
-        terminated(separated_pair(position, space1, velocity), opt(newline))(input)?;
+    let (rem, (p, v)) = separated_pair(position, space1, velocity)(input)?;
     Ok((
         rem,
         Robot {
@@ -131,22 +125,11 @@ pub fn robot(input: &str) -> IResult<&str, Robot> {
 }
 
 pub fn position(input: &str) -> IResult<&str, (usize, usize)> {
-    let (input, _) = tag("p=")(input)?;
-    let (rem, (x, y)) = separated_pair(digit1, tag(","), digit1)(input)?;
-    Ok((
-        rem,
-        (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()),
-    ))
+    preceded(tag("p="), coordinates)(input)
 }
 
 pub fn velocity(input: &str) -> IResult<&str, (isize, isize)> {
-    let (input, _) = tag("v=")(input)?;
-    separated_pair(signed_number, tag(","), signed_number)(input)
-}
-
-pub fn signed_number(input: &str) -> IResult<&str, isize> {
-    let (input, string) = recognize(preceded(opt(tag("-")), digit1))(input)?;
-    Ok((input, string.parse::<isize>().unwrap()))
+    preceded(tag("v="), coordinates)(input)
 }
 
 pub struct Robot {
@@ -161,7 +144,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(12));
     }
 
     #[test]
@@ -1,6 +1,8 @@
-use std::{
-    collections::HashMap,
-    io::{self, Read},
+use std::io::{self, Read};
+
+use advent_of_code::{
+    grid::Grid,
+    point::{Direction, Point},
 };
 
 advent_of_code::solution!(15);
@@ -10,8 +12,8 @@ pub fn part_one(input: &str) -> Option<isize> {
     let mut map = parse_map(input_parts[0]);
     let path = input_parts[1];
     path.chars().for_each(|c| {
-        if c != '\n' {
-            step_map(&mut map, c);
+        if let Ok(step) = Direction::try_from(c) {
+            step_map(&mut map, step);
         }
     });
     Some(compute_score(map))
@@ -30,35 +32,26 @@ fn pause() {
 
 pub fn compute_score(map: Map) -> isize {
     map.map
-        .into_iter()
-        .filter(|(_, o)| *o == Object::Crate_Left)
-        .map(|((x, y), _)| 100 * y + x)
+        .iter()
+        .filter(|(_, o)| **o == Object::Crate_Left)
+        .map(|(p, _)| (100 * p.y + p.x) as isize)
         .sum()
 }
 
 pub fn print_map(map_data: Map) -> String {
-    let mut map: String = String::new();
-    (0..map_data.size.1).for_each(|y| {
-        (0..2 * map_data.size.0).for_each(|x| {
-            if map_data.robot_pos == (x, y) {
-                map.push('@');
-            } else {
-                map.push(match *map_data.map.get(&(x, y)).unwrap() {
-                    Object::Wall => '#',
-                    Object::Empty => '.',
-                    Object::Crate_Left => '[',
-                    Object::Crate_Right => ']',
-                });
-            }
-        });
-        map.push('\n');
+    let mut map = map_data.map.map(|o| match o {
+        Object::Wall => '#',
+        Object::Empty => '.',
+        Object::Crate_Left => '[',
+        Object::Crate_Right => ']',
     });
-    map
+    map[map_data.robot_pos] = '@';
+    map.to_string()
 }
 
-pub fn step_map(map: &mut Map, step: char) -> &mut Map {
+pub fn step_map(map: &mut Map, step: Direction) -> &mut Map {
     let new_pos = move_pos(map.robot_pos, step);
-    match *map.map.get(&new_pos).unwrap() {
+    match map.map[new_pos] {
         Object::Wall => map,
         Object::Empty => {
             map.robot_pos = new_pos;
@@ -73,7 +66,7 @@ pub fn step_map(map: &mut Map, step: char) -> &mut Map {
             }
         }
         Object::Crate_Right => {
-            if move_crate(map, step, (new_pos.0 - 1, new_pos.1), true) {
+            if move_crate(map, step, new_pos + Direction::West.vector(), true) {
                 map.robot_pos = new_pos;
                 map
             } else {
@@ -83,18 +76,18 @@ pub fn step_map(map: &mut Map, step: char) -> &mut Map {
     }
 }
 
-pub fn compute_second_pos(pos: (isize, isize)) -> (isize, isize) {
-    (pos.0 + 1, pos.1)
+pub fn compute_second_pos(pos: Point) -> Point {
+    pos + Direction::East.vector()
 }
 
-pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it: bool) -> bool {
+pub fn move_crate(map: &mut Map, step: Direction, current_pos: Point, do_it: bool) -> bool {
     let new_crate_pos = move_pos(current_pos, step);
 
     let (c1, c2) = (
-        *map.map.get(&new_crate_pos).unwrap(),
-        *map.map.get(&compute_second_pos(new_crate_pos)).unwrap(),
+        map.map[new_crate_pos],
+        map.map[compute_second_pos(new_crate_pos)],
     );
-    if (step == '<') {
+    if (step == Direction::West) {
         match c1 {
             Object::Wall => false,
             Object::Empty => {
@@ -105,7 +98,7 @@ pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it:
             }
             Object::Crate_Left => panic!("Not possible"),
             Object::Crate_Right => {
-                if (move_crate(map, step, (new_crate_pos.0 - 1, new_crate_pos.1), do_it)) {
+                if (move_crate(map, step, new_crate_pos + Direction::West.vector(), do_it)) {
                     if (do_it) {
                         do_move_crate(map, new_crate_pos, current_pos);
                     }
@@ -115,7 +108,7 @@ pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it:
                 }
             }
         }
-    } else if (step == '>') {
+    } else if (step == Direction::East) {
         match c2 {
             Object::Wall => false,
             Object::Empty => {
@@ -164,7 +157,7 @@ pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it:
                 }
             }
             (Object::Crate_Right, Object::Empty) => {
-                if move_crate(map, step, (new_crate_pos.0 - 1, new_crate_pos.1), do_it) {
+                if move_crate(map, step, new_crate_pos + Direction::West.vector(), do_it) {
                     if (do_it) {
                         do_move_crate(map, new_crate_pos, current_pos);
                     }
@@ -174,11 +167,11 @@ pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it:
                 }
             }
             (Object::Crate_Right, Object::Crate_Left) => {
-                if move_crate(map, step, (new_crate_pos.0 - 1, new_crate_pos.1), false)
+                if move_crate(map, step, new_crate_pos + Direction::West.vector(), false)
                     && move_crate(map, step, compute_second_pos(new_crate_pos), false)
                 {
                     if (do_it) {
-                        move_crate(map, step, (new_crate_pos.0 - 1, new_crate_pos.1), do_it);
+                        move_crate(map, step, new_crate_pos + Direction::West.vector(), do_it);
                         move_crate(map, step, compute_second_pos(new_crate_pos), do_it);
                         do_move_crate(map, new_crate_pos, current_pos);
                     }
@@ -192,59 +185,43 @@ pub fn move_crate(map: &mut Map, step: char, current_pos: (isize, isize), do_it:
     }
 }
 
-fn do_move_crate(
-    map: &mut Map,
-    new_crate_pos: (isize, isize),
-    current_pos: (isize, isize),
-) -> bool {
-    map.map.insert(current_pos, Object::Empty);
-    map.map
-        .insert(compute_second_pos(current_pos), Object::Empty);
-    map.map.insert(new_crate_pos, Object::Crate_Left);
-    map.map
-        .insert(compute_second_pos(new_crate_pos), Object::Crate_Right);
+fn do_move_crate(map: &mut Map, new_crate_pos: Point, current_pos: Point) -> bool {
+    map.map[current_pos] = Object::Empty;
+    map.map[compute_second_pos(current_pos)] = Object::Empty;
+    map.map[new_crate_pos] = Object::Crate_Left;
+    map.map[compute_second_pos(new_crate_pos)] = Object::Crate_Right;
     true
 }
 
-pub fn move_pos(pos: (isize, isize), dir: char) -> (isize, isize) {
-    match dir {
-        '^' => (pos.0, pos.1 - 1),
-        '>' => (pos.0 + 1, pos.1),
-        '<' => (pos.0 - 1, pos.1),
-        'v' => (pos.0, pos.1 + 1),
-        _ => panic!("Not a valid dir"),
-    }
+pub fn move_pos(pos: Point, dir: Direction) -> Point {
+    pos + dir.vector()
 }
 
 pub fn parse_map(input: &str) -> Map {
+    let tiles = Grid::parse(input, |c| c);
     let mut map: Map = Map {
-        map: HashMap::new(),
-        robot_pos: (0, 0),
-        size: (0, 0),
+        map: Grid::new(2 * tiles.width(), tiles.height(), Object::Empty),
+        robot_pos: Point::new(0, 0),
     };
-    input.lines().enumerate().for_each(|(y, line)| {
-        map.size.1 += 1;
-        map.size.0 = line.len() as isize;
-        line.chars().enumerate().for_each(|(x, c)| {
-            let (x, y) = (x as isize, y as isize);
-            if c == '@' {
-                map.robot_pos = (2 * x, y);
+    tiles.iter().for_each(|(pos, c)| {
+        let (left, right) = (
+            Point::new(2 * pos.x, pos.y),
+            Point::new(2 * pos.x + 1, pos.y),
+        );
+        if *c == '@' {
+            map.robot_pos = left;
+        }
+        match c {
+            '#' => {
+                map.map[left] = Object::Wall;
+                map.map[right] = Object::Wall;
             }
-            match c {
-                '#' => {
-                    map.map.insert((2 * x, y), Object::Wall);
-                    map.map.insert((2 * x + 1, y), Object::Wall);
-                }
-                'O' => {
-                    map.map.insert((2 * x, y), Object::Crate_Left);
-                    map.map.insert((2 * x + 1, y), Object::Crate_Right);
-                }
-                _ => {
-                    map.map.insert((2 * x, y), Object::Empty);
-                    map.map.insert((2 * x + 1, y), Object::Empty);
-                }
-            };
-        });
+            'O' => {
+                map.map[left] = Object::Crate_Left;
+                map.map[right] = Object::Crate_Right;
+            }
+            _ => {}
+        };
     });
 
     map
@@ -260,9 +237,8 @@ pub enum Object {
 
 #[derive(Clone)]
 pub struct Map {
-    robot_pos: (isize, isize),
-    map: HashMap<(isize, isize), Object>,
-    size: (isize, isize),
+    robot_pos: Point,
+    map: Grid<Object>,
 }
 
 #[cfg(test)]
@@ -1,35 +1,20 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+use advent_of_code::{
+    grid::Grid,
+    point::{Direction, Point},
+};
+
 advent_of_code::solution!(16);
 
 pub fn part_one(input: &str) -> Option<isize> {
-    let mut start_pos: (isize, isize) = (0, 0);
-    let mut end_pos: (isize, isize) = (0, 0);
-    let maze: Vec<Vec<bool>> = input
-        .lines()
-        .enumerate()
-        .map(|(y, c)| {
-            c.chars()
-                .enumerate()
-                .map(|(x, ch)| match (ch) {
-                    '#' => true,
-                    'S' => {
-                        start_pos = (x.clone() as isize, y.clone() as isize);
-                        false
-                    }
-                    'E' => {
-                        end_pos = (x.clone() as isize, y.clone() as isize);
-                        false
-                    }
-                    _ => false,
-                })
-                .collect()
-        })
-        .collect();
+    let maze = Grid::parse(input, |c| c);
+    let start_pos = maze.find(&'S')?;
+    let end_pos = maze.find(&'E')?;
     Some(find_path(
-        maze,
+        maze.map(|&c| c == '#'),
         Deer {
-            direction: Direction::EAST,
+            direction: Direction::East,
             pos: start_pos,
         },
         end_pos,
@@ -40,7 +25,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     None
 }
 
-pub fn find_path(maze: Vec<Vec<bool>>, mut deer_pos: Deer, end_pos: (isize, isize)) -> isize {
+pub fn find_path(maze: Grid<bool>, mut deer_pos: Deer, end_pos: Point) -> isize {
     let mut visited: HashSet<Deer> = HashSet::new();
     let mut frontier: BinaryHeap<Node> = BinaryHeap::new();
     let dist: &mut HashMap<Deer, isize> = &mut HashMap::<Deer, isize>::new();
@@ -53,12 +38,7 @@ pub fn find_path(maze: Vec<Vec<bool>>, mut deer_pos: Deer, end_pos: (isize, isiz
         }
         visited.insert(deer_pos);
         for (deer, cost) in compute_neighbors(deer_pos) {
-            let wall = *maze
-                .get(deer.pos.1 as usize)
-                .unwrap()
-                .get(deer.pos.0 as usize)
-                .unwrap();
-            if wall {
+            if maze[deer.pos] {
                 continue;
             }
             if visited.contains(&deer) {
@@ -81,98 +61,21 @@ pub fn find_path(maze: Vec<Vec<bool>>, mut deer_pos: Deer, end_pos: (isize, isiz
 }
 
 pub fn compute_neighbors(deer: Deer) -> Vec<(Deer, isize)> {
-    let mut neigh = Vec::new();
-    match deer.direction {
-        Direction::EAST => neigh.push((
+    let turn = |direction| Deer {
+        direction,
+        pos: deer.pos,
+    };
+    vec![
+        (
             Deer {
-                direction: Direction::EAST,
-                pos: (deer.pos.0 + 1, deer.pos.1),
+                direction: deer.direction,
+                pos: deer.pos + deer.direction.vector(),
             },
             1,
-        )),
-        Direction::WEST => neigh.push((
-            Deer {
-                direction: Direction::WEST,
-                pos: (deer.pos.0 - 1, deer.pos.1),
-            },
-            1,
-        )),
-        Direction::NORTH => neigh.push((
-            Deer {
-                direction: Direction::NORTH,
-                pos: (deer.pos.0, deer.pos.1 - 1),
-            },
-            1,
-        )),
-        Direction::SOUTH => neigh.push((
-            Deer {
-                direction: Direction::SOUTH,
-                pos: (deer.pos.0, deer.pos.1 + 1),
-            },
-            1,
-        )),
-    }
-    match deer.direction {
-        Direction::EAST => neigh.push((
-            Deer {
-                direction: Direction::NORTH,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::WEST => neigh.push((
-            Deer {
-                direction: Direction::NORTH,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::NORTH => neigh.push((
-            Deer {
-                direction: Direction::EAST,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::SOUTH => neigh.push((
-            Deer {
-                direction: Direction::EAST,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-    }
-    match deer.direction {
-        Direction::EAST => neigh.push((
-            Deer {
-                direction: Direction::SOUTH,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::WEST => neigh.push((
-            Deer {
-                direction: Direction::SOUTH,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::NORTH => neigh.push((
-            Deer {
-                direction: Direction::WEST,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-        Direction::SOUTH => neigh.push((
-            Deer {
-                direction: Direction::WEST,
-                pos: (deer.pos.0, deer.pos.1),
-            },
-            1000,
-        )),
-    }
-    neigh
+        ),
+        (turn(deer.direction.turn_left()), 1000),
+        (turn(deer.direction.turn_right()), 1000),
+    ]
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -196,15 +99,7 @@ impl Ord for Node {
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Deer {
     direction: Direction,
-    pos: (isize, isize),
-}
-
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
-pub enum Direction {
-    EAST,
-    WEST,
-    NORTH,
-    SOUTH,
+    pos: Point,
 }
 
 #[cfg(test)]
@@ -214,7 +109,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(11048));
     }
 
     #[test]
@@ -1,195 +1,186 @@
-use std::collections::HashMap;
+use std::fmt::Display;
 
+use advent_of_code::parse::{key_value, lines_of, parse_or_report, unsigned};
+use advent_of_code::vm::{self, search_backwards, Effect, InstructionSet, Registers, Vm};
+use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{digit1, newline},
-    combinator::{map_res, opt},
-    multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair, terminated},
+    character::complete::multispace1,
+    combinator::value,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
     IResult,
 };
-use num::Integer;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, parse = parse);
 
 pub fn part_one(input: &str) -> Option<String> {
-    let (rem, registers) = many1(register)(input).unwrap();
-    let (rem, _) = newline::<_, nom::error::Error<_>>(rem).unwrap();
-    let (rem, program) = program(rem).unwrap();
-    let mut registers: HashMap<&str, u64> = registers.iter().map(|c| *c).collect();
-    let mut addr = 0;
-    let mut result_list: Vec<u64> = Vec::new();
-    while (addr < program.len()) {
-        let (next, result) = run_instruction(&mut registers, &program, addr);
-        if result.is_some() {
-            result_list.push(result.unwrap());
-        }
-        addr = next;
-    }
-    let results: String = result_list
-        .iter()
-        .map(|i| i.to_string())
-        .collect::<Vec<String>>()
-        .join(",");
-    Some(results)
+    let (registers, program) = parse_or_report(parse, input)?;
+    let mut vm = Vm::<Computer>::new(&program, registers);
+    Some(vm.run().iter().join(","))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (rem, registers) = many1(register)(input).unwrap();
-    let (rem, _) = newline::<_, nom::error::Error<_>>(rem).unwrap();
-    let (rem, program) = program(rem).unwrap();
-    let registers: HashMap<&str, u64> = registers.iter().map(|c| *c).collect();
-    let test_value = program
-        .iter()
-        .flat_map(|(i, j)| vec![*i as u64, *j as u64])
-        .collect::<Vec<u64>>();
-    let mut value: u64 = 37213107740000;
-    loop {
-        let mut register_impl = registers.clone();
-        register_impl.insert("A", value);
-        let result = run_program(&mut register_impl, &program);
-        if result == test_value {
-            return Some(value);
-        }
-        if value % 10000 == 0 {
-            println!("{} : {:?}", value, result);
-        }
-        if result.len() < 16 {
-            value += 100000;
+    let (registers, program) = parse_or_report(parse, input)?;
+    // the program outputs a value for every 3 bits of A, which it shifts out on each iteration.
+    search_backwards(&program, 3, |a| {
+        let mut registers = registers;
+        registers[Register::A] = a;
+        Vm::<Computer>::new(&program, registers).run().to_vec()
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl vm::Register for Register {
+    const ALL: &'static [Self] = &[Register::A, Register::B, Register::C];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operand {
+    Literal(u8),
+    Register(Register),
+}
+
+impl Operand {
+    fn combo(operand: u8) -> Self {
+        match operand {
+            0..=3 => Operand::Literal(operand),
+            4 => Operand::Register(Register::A),
+            5 => Operand::Register(Register::B),
+            6 => Operand::Register(Register::C),
+            _ => panic!("Invalid combo operand {operand}"),
         }
-        if result[12..] == vec![0, 3, 3, 0] {
-            if result[8..] == vec![1, 3, 5, 5, 0, 3, 3, 0] {
-                if result[4..] == vec![7, 5, 4, 7, 1, 3, 5, 5, 0, 3, 3, 0] {
-                    println!("Reached {:?} at {}", result, value);
-                    value += 1;
-                } else {
-                    value += 10000;
-                }
-            } else {
-                value += 100000;
-            }
-        } else {
-            value += 10000000;
+    }
+
+    fn value(self, registers: &Registers<Register, u64, 3>) -> u64 {
+        match self {
+            Operand::Literal(value) => u64::from(value),
+            Operand::Register(register) => registers[register],
         }
     }
 }
 
-pub fn run_program(mut registers: &mut HashMap<&str, u64>, program: &Vec<(u8, u8)>) -> Vec<u64> {
-    let mut addr = 0;
-    let mut result_list: Vec<u64> = Vec::new();
-    while (addr < program.len()) {
-        let (next, result) = run_instruction(&mut registers, &program, addr);
-        if result.is_some() {
-            result_list.push(result.unwrap());
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operand::Literal(value) => write!(f, "{value}"),
+            Operand::Register(register) => write!(f, "{register:?}"),
         }
-        addr = next;
     }
-    result_list
 }
 
-pub fn eval_operand(registers: &HashMap<&str, u64>, opcode: u8) -> u64 {
-    match opcode {
-        0 => 0,
-        1 => 1,
-        2 => 2,
-        3 => 3,
-        4 => *registers.get("A").unwrap(),
-        5 => *registers.get("B").unwrap(),
-        6 => *registers.get("C").unwrap(),
-        _ => panic!("Invalid opcode"),
-    }
+#[derive(Debug, Clone, Copy)]
+pub enum Instruction {
+    Adv(Operand),
+    Bxl(u8),
+    Bst(Operand),
+    Jnz(u8),
+    Bxc,
+    Out(Operand),
+    Bdv(Operand),
+    Cdv(Operand),
 }
 
-pub fn run_instruction(
-    registers: &mut HashMap<&str, u64>,
-    program: &Vec<(u8, u8)>,
-    idx: usize,
-) -> (usize, Option<u64>) {
-    let (instr, opcode) = program.get(idx).unwrap();
-    match instr {
-        0 => {
-            registers.insert(
-                "A",
-                registers
-                    .get("A")
-                    .unwrap()
-                    .div_floor(&2u64.pow(eval_operand(registers, *opcode).try_into().unwrap())),
-            );
-            (idx + 1, None)
-        }
-        1 => {
-            registers.insert(
-                "B",
-                registers.get("B").unwrap() ^ (eval_operand(registers, *opcode)),
-            );
-            (idx + 1, None)
-        }
-        2 => {
-            registers.insert("B", eval_operand(registers, *opcode) % 8);
-            (idx + 1, None)
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Instruction::Adv(op) => write!(f, "adv {op:<4} A = A >> {op}"),
+            Instruction::Bxl(l) => write!(f, "bxl {l:<4} B = B ^ {l}"),
+            Instruction::Bst(op) => write!(f, "bst {op:<4} B = {op} % 8"),
+            Instruction::Jnz(l) => write!(f, "jnz {l:<4} if A != 0 goto {l}"),
+            Instruction::Bxc => write!(f, "bxc      B = B ^ C"),
+            Instruction::Out(op) => write!(f, "out {op:<4} output {op} % 8"),
+            Instruction::Bdv(op) => write!(f, "bdv {op:<4} B = A >> {op}"),
+            Instruction::Cdv(op) => write!(f, "cdv {op:<4} C = A >> {op}"),
         }
-        3 => {
-            let jmp = registers.get("A").unwrap();
-            if *jmp == 0 {
-                return (idx + 1, None);
-            } else {
-                return (eval_operand(registers, *opcode) as usize, None);
-            }
-        }
-        4 => {
-            registers.insert(
-                "B",
-                registers.get("B").unwrap() ^ registers.get("C").unwrap(),
-            );
-            (idx + 1, None)
-        }
-        5 => (idx + 1, Some(eval_operand(registers, *opcode) % 8)),
-        6 => {
-            registers.insert(
-                "B",
-                registers
-                    .get("A")
-                    .unwrap()
-                    .div_floor(&2u64.pow(eval_operand(registers, *opcode).try_into().unwrap())),
-            );
-            (idx + 1, None)
-        }
-        7 => {
-            registers.insert(
-                "C",
-                registers
-                    .get("A")
-                    .unwrap()
-                    .div_floor(&2u64.pow(eval_operand(registers, *opcode).try_into().unwrap())),
-            );
-            (idx + 1, None)
+    }
+}
+
+/// The 3-bit computer: every instruction is an opcode followed by an operand.
+pub struct Computer;
+
+impl InstructionSet for Computer {
+    type Word = u8;
+    type Registers = Registers<Register, u64, 3>;
+    type Instruction = Instruction;
+    type Output = u8;
+
+    fn decode(program: &[u8], address: usize) -> Option<(Instruction, usize)> {
+        let (&opcode, &operand) = (program.get(address)?, program.get(address + 1)?);
+        let instruction = match opcode {
+            0 => Instruction::Adv(Operand::combo(operand)),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(Operand::combo(operand)),
+            3 => Instruction::Jnz(operand),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(Operand::combo(operand)),
+            6 => Instruction::Bdv(Operand::combo(operand)),
+            7 => Instruction::Cdv(Operand::combo(operand)),
+            _ => panic!("Invalid instruction {opcode}"),
+        };
+        Some((instruction, 2))
+    }
+
+    fn execute(instruction: Instruction, registers: &mut Self::Registers) -> Effect<u8> {
+        let shift = |registers: &Self::Registers, op: Operand| {
+            let amount = u32::try_from(op.value(registers)).unwrap_or(u32::MAX);
+            registers[Register::A].checked_shr(amount).unwrap_or(0)
+        };
+
+        match instruction {
+            Instruction::Adv(op) => registers[Register::A] = shift(registers, op),
+            Instruction::Bxl(l) => registers[Register::B] ^= u64::from(l),
+            Instruction::Bst(op) => registers[Register::B] = op.value(registers) % 8,
+            Instruction::Jnz(l) if registers[Register::A] != 0 => return Effect::Jump(l.into()),
+            Instruction::Jnz(_) => {}
+            Instruction::Bxc => registers[Register::B] ^= registers[Register::C],
+            Instruction::Out(op) => return Effect::Output((op.value(registers) % 8) as u8),
+            Instruction::Bdv(op) => registers[Register::B] = shift(registers, op),
+            Instruction::Cdv(op) => registers[Register::C] = shift(registers, op),
         }
-        _ => panic!("Invalid instruction"),
+        Effect::Next
     }
 }
 
-pub fn program(input: &str) -> IResult<&str, Vec<(u8, u8)>> {
-    preceded(
-        tag("Program: "),
-        separated_list1(
-            tag(","),
-            separated_pair(
-                map_res(digit1, |c: &str| c.parse::<u8>()),
-                tag(","),
-                map_res(digit1, |c: &str| c.parse::<u8>()),
-            ),
-        ),
-    )(input)
+pub fn parse(input: &str) -> IResult<&str, (Registers<Register, u64, 3>, Vec<u8>)> {
+    let (rest, (values, program)) =
+        separated_pair(lines_of(register), multispace1, program)(input)?;
+
+    let mut registers = Registers::default();
+    for (register, value) in values {
+        registers[register] = value;
+    }
+    Ok((rest, (registers, program)))
 }
 
-pub fn register(input: &str) -> IResult<&str, (&str, u64)> {
-    let (rem, _) = tag("Register ")(input)?;
-    let (rem, reg_name) = alt((tag("A"), tag("B"), tag("C")))(rem)?;
-    let (rem, _) = tag(": ")(rem)?;
-    let (rem, value) = map_res(digit1, |c: &str| c.parse::<u64>())(rem)?;
-    let (rem, _) = newline(rem)?;
-    Ok((rem, (reg_name, value)))
+pub fn program(input: &str) -> IResult<&str, Vec<u8>> {
+    preceded(tag("Program: "), separated_list1(tag(","), unsigned))(input)
+}
+
+pub fn register(input: &str) -> IResult<&str, (Register, u64)> {
+    key_value(
+        preceded(
+            tag("Register "),
+            alt((
+                value(Register::A, tag("A")),
+                value(Register::B, tag("B")),
+                value(Register::C, tag("C")),
+            )),
+        ),
+        ":",
+        unsigned,
+    )(input)
 }
 
 #[cfg(test)]
@@ -199,12 +190,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some("5,7,3,0".into()));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(117440));
     }
 }
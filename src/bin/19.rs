@@ -1,5 +1,4 @@
-use std::collections::{HashMap, HashSet};
-
+use advent_of_code::{parse::parse_or_report, trie::Trie};
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, newline},
@@ -10,77 +9,16 @@ use nom::{
 
 advent_of_code::solution!(19);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (towels, targets) = pattern(input).unwrap().1;
-    let mut trie = TrieNode {
-        is_end: false,
-        childs: HashMap::new(),
-    };
-    towels.iter().enumerate().for_each(|(i, prefix)| {
-        println!("Parsing {} idx : {}", prefix, i);
-        add_to_trie(prefix, &mut trie);
-    });
-    Some(
-        targets
-            .iter()
-            .map(|t| recursive_find(t, &trie.childs, &trie, &mut HashMap::new()))
-            .sum(),
-    )
-}
-
-pub fn recursive_find(
-    word: &str,
-    sub_trie: &HashMap<char, TrieNode>,
-    whole_trie: &TrieNode,
-    eliminated: &mut HashMap<String, u64>,
-) -> u64 {
-    let next = sub_trie.get(&word.chars().nth(0).unwrap());
-    if next.is_none() {
-        return 0;
-    }
-    let next_node = next.unwrap();
-    if word.len() == 1 {
-        if next_node.is_end {
-            return 1;
-        } else {
-            return 0;
-        }
-    }
-    let s = &word[1..];
-    let finishes = recursive_find(s, &next_node.childs, &whole_trie, eliminated);
-    if next_node.is_end {
-        if eliminated.contains_key(s) {
-            return finishes + eliminated.get(s).unwrap();
-        }
-        let whole_computation = recursive_find(s, &whole_trie.childs, whole_trie, eliminated);
-        eliminated.insert(s.to_string(), whole_computation);
-        return whole_computation + finishes;
-    }
-    return finishes;
-}
-
-pub fn add_to_trie(word: &str, current_node: &mut TrieNode) {
-    if word.len() == 0 {
-        current_node.is_end = true;
-        return;
-    }
-    let first_letter = word.chars().nth(0).unwrap();
-    add_to_trie(
-        &word[1..],
-        &mut current_node.childs.entry(first_letter).or_insert(TrieNode {
-            is_end: false,
-            childs: HashMap::new(),
-        }),
-    );
-}
-
-pub struct TrieNode {
-    is_end: bool,
-    childs: HashMap<char, TrieNode>,
+pub fn part_one(input: &str) -> Option<usize> {
+    let (towels, targets) = parse_or_report(pattern, input)?;
+    let trie: Trie = towels.into_iter().collect();
+    Some(targets.iter().filter(|t| trie.can_segment(t)).count())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_two(input: &str) -> Option<u64> {
+    let (towels, targets) = parse_or_report(pattern, input)?;
+    let trie: Trie = towels.into_iter().collect();
+    Some(targets.iter().map(|t| trie.count_segmentations(t)).sum())
 }
 
 pub fn pattern(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
@@ -107,48 +45,28 @@ mod tests {
 
     #[test]
     fn test_build_trie() {
-        let words = vec!["ab", "ac"];
-        let mut trie = TrieNode {
-            is_end: false,
-            childs: HashMap::new(),
-        };
-        words.iter().for_each(|prefix| {
-            add_to_trie(prefix, &mut trie);
-        });
-        assert_eq!(trie.childs.len(), 1);
-        let a_child = trie.childs.get(&'a').unwrap();
-        assert_eq!(a_child.is_end, false);
-        assert_eq!(a_child.childs.len(), 2);
-        let c_child = a_child.childs.get(&'c').unwrap();
-        assert_eq!(c_child.is_end, true);
-        assert_eq!(c_child.childs.len(), 0);
+        let trie: Trie = ["ab", "ac"].into_iter().collect();
+        assert_eq!(trie.len(), 2);
+        assert!(trie.has_prefix("a"));
+        assert!(!trie.contains("a"));
+        assert!(trie.contains("ac"));
+        assert!(!trie.has_prefix("acd"));
     }
 
     #[test]
     fn test_build_trie_2() {
-        let words = vec!["ab", "ac", "acd", "e", "acd"];
-        let mut trie = TrieNode {
-            is_end: false,
-            childs: HashMap::new(),
-        };
-        words.iter().for_each(|prefix| {
-            add_to_trie(prefix, &mut trie);
-        });
-        assert_eq!(trie.childs.len(), 2);
-        let e_child = trie.childs.get(&'e').unwrap();
-        assert_eq!(e_child.is_end, true);
-        assert_eq!(e_child.childs.len(), 0);
-        let a_child = trie.childs.get(&'a').unwrap();
-        assert_eq!(a_child.is_end, false);
-        assert_eq!(a_child.childs.len(), 2);
-        let c_child = a_child.childs.get(&'c').unwrap();
-        assert_eq!(c_child.is_end, true);
-        assert_eq!(c_child.childs.len(), 1);
+        let trie: Trie = ["ab", "ac", "acd", "e", "acd"].into_iter().collect();
+        assert_eq!(trie.len(), 4);
+        assert!(trie.contains("e"));
+        assert!(!trie.has_prefix("ea"));
+        assert!(!trie.contains("a"));
+        assert!(trie.contains("ac"));
+        assert!(trie.contains("acd"));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(16));
     }
 }
@@ -1,45 +1,30 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    ops::BitXor,
-    u128,
-};
+use std::{collections::VecDeque, ops::BitXor, u128};
+
+use advent_of_code::collections::{FastMap, FastSet, Pack};
 
 advent_of_code::solution!(22);
 
 pub fn part_one(input: &str) -> Option<u128> {
     let input_numbers = input.lines().map(|c| c.parse::<u128>().unwrap());
     let result = input_numbers
-        .map(|c| compute_iter(c, 2000, &mut HashMap::new()))
+        .map(|c| compute_iter(c, 2000, &mut FastMap::default()))
         .sum();
     Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<u16> {
     let input_numbers = input.lines().map(|c| c.parse::<u128>().unwrap());
-    let values = &mut HashMap::new();
+    let values = &mut FastMap::default();
     let _ = input_numbers.for_each(|c| {
         compute_iter(c, 2000, values);
     });
-    let mut max = 0;
-    let mut result = (0, 0, 0, 0);
-    for (k, v) in values {
-        if *v > max {
-            result = *k;
-            max = *v;
-        }
-    }
-    println!("{:?}", result);
-    Some(max)
+    Some(values.values().copied().max().unwrap_or(0))
 }
 
-pub fn compute_iter(
-    mut secret: u128,
-    iteration: u16,
-    values: &mut HashMap<(i16, i16, i16, i16), u16>,
-) -> u128 {
+pub fn compute_iter(mut secret: u128, iteration: u16, values: &mut FastMap<u64, u16>) -> u128 {
     let init_secret = secret;
     let mut change_list = VecDeque::new();
-    let mut visited: HashSet<(i16, i16, i16, i16)> = HashSet::new();
+    let mut visited: FastSet<u64> = FastSet::default();
     for i in (0..3) {
         let (new_secret, _, change) = compute_next(secret);
         secret = new_secret;
@@ -54,7 +39,8 @@ pub fn compute_iter(
             change_list[1],
             change_list[2],
             change_list[3],
-        );
+        )
+            .pack();
         secret = new_secret;
         change_list.pop_front();
         if visited.contains(&test) {
@@ -94,7 +80,7 @@ mod tests {
     fn test_iter() {
         let result: Vec<u128> = vec![1, 10, 100, 2024]
             .iter()
-            .map(|c| compute_iter(*c, 2000, &mut HashMap::new()))
+            .map(|c| compute_iter(*c, 2000, &mut FastMap::default()))
             .collect();
         assert_eq!(result, vec![8685429, 4700978, 15273692, 8667524]);
     }
@@ -0,0 +1,287 @@
+//! Collections for hot paths: hash maps with a fast hasher, a bit set for the cells of a grid
+//! and packing of small tuples into a single integer key.
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hasher},
+};
+
+use crate::{grid::Grid, point::Point};
+
+/// A fast, non-cryptographic hasher in the style of the `FxHasher` of rustc and Firefox.
+///
+/// It mixes in a word at a time, which is a lot faster than the default SipHash for integer and
+/// tuple keys. It is not resistant to collisions chosen by an attacker, which puzzle inputs are not.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        // the multiplication only carries upwards, so the low bits that pick a bucket would only
+        // depend on the low bits of the key. Packed keys differ in their high bits as well.
+        self.hash.rotate_left(26)
+    }
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// A `HashMap` with the [`FxHasher`]. Create it with `FastMap::default()`.
+pub type FastMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` with the [`FxHasher`]. Create it with `FastSet::default()`.
+pub type FastSet<T> = HashSet<T, FxBuildHasher>;
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of points on a grid that uses a single bit per cell, e.g. for visited cells.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates an empty set for the points of a `width` by `height` grid.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(64)],
+        }
+    }
+
+    /// Creates an empty set for the points of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn bit(&self, point: Point) -> Option<(usize, u64)> {
+        let inside =
+            (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y);
+        inside.then(|| {
+            let index = point.y as usize * self.width + point.x as usize;
+            (index / 64, 1 << (index % 64))
+        })
+    }
+
+    /// Adds `point`, returning whether it was not in the set before.
+    ///
+    /// # Panics
+    /// If `point` is outside of the grid.
+    pub fn insert(&mut self, point: Point) -> bool {
+        let (word, mask) = self
+            .bit(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"));
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        added
+    }
+
+    /// Removes `point`, returning whether it was in the set.
+    pub fn remove(&mut self, point: Point) -> bool {
+        let Some((word, mask)) = self.bit(point) else {
+            return false;
+        };
+        let removed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        removed
+    }
+
+    /// Whether `point` is in the set. Points outside of the grid never are.
+    pub fn contains(&self, point: Point) -> bool {
+        self.bit(point)
+            .is_some_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    /// The number of points in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Iterates over the points in the set, line by line.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let mut rest = word;
+                std::iter::from_fn(move || {
+                    (rest != 0).then(|| {
+                        let bit = rest.trailing_zeros() as usize;
+                        rest &= rest - 1;
+                        let cell = index * 64 + bit;
+                        Point::new((cell % self.width) as i64, (cell / self.width) as i64)
+                    })
+                })
+            })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Packs a small tuple into a single `u64`, which hashes and compares faster than the tuple,
+/// e.g. `(-1, 2, 0, 3).pack()` as the key of a [`FastMap`].
+///
+/// Implemented for tuples of up to four 8 or 16 bit integers and pairs of 32 bit integers.
+/// Packing keeps the bits of every element, so `unpack` restores the tuple.
+pub trait Pack: Sized {
+    fn pack(self) -> u64;
+
+    fn unpack(key: u64) -> Self;
+}
+
+macro_rules! impl_pack {
+    ($t:ty as $u:ty: $(($($field:tt),+)),+) => {
+        $(
+            impl Pack for ($(impl_pack!(@type $t, $field),)+) {
+                #[inline]
+                fn pack(self) -> u64 {
+                    let mut key = 0;
+                    $( key = (key << <$u>::BITS) | u64::from(self.$field as $u); )+
+                    key
+                }
+
+                #[inline]
+                fn unpack(key: u64) -> Self {
+                    const LAST: u32 = [$($field),+].len() as u32 - 1;
+                    ($( (key >> ((LAST - $field) * <$u>::BITS)) as $u as $t, )+)
+                }
+            }
+        )+
+    };
+    (@type $t:ty, $field:tt) => { $t };
+}
+
+impl_pack!(u8 as u8: (0, 1), (0, 1, 2), (0, 1, 2, 3));
+impl_pack!(i8 as u8: (0, 1), (0, 1, 2), (0, 1, 2, 3));
+impl_pack!(u16 as u16: (0, 1), (0, 1, 2), (0, 1, 2, 3));
+impl_pack!(i16 as u16: (0, 1), (0, 1, 2), (0, 1, 2, 3));
+impl_pack!(u32 as u32: (0, 1));
+impl_pack!(i32 as u32: (0, 1));
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hash::{BuildHasher, Hash};
+
+    use super::*;
+
+    fn fx_hash(value: impl Hash) -> u64 {
+        FxBuildHasher::default().hash_one(value)
+    }
+
+    #[test]
+    fn hashes_values() {
+        assert_eq!(fx_hash(1_u64), fx_hash(1_u64));
+        assert_ne!(fx_hash(1_u64), fx_hash(2_u64));
+        assert_ne!(fx_hash((1, 2)), fx_hash((2, 1)));
+        assert_ne!(fx_hash("abcdefghi"), fx_hash("abcdefghj"));
+        // keys that only differ in their high bits end up in different buckets.
+        assert_ne!(fx_hash(1_u64 << 32) & 0xff, fx_hash(2_u64 << 32) & 0xff);
+
+        let mut map: FastMap<(i16, i16), u32> = FastMap::default();
+        *map.entry((1, -1)).or_default() += 2;
+        *map.entry((1, -1)).or_default() += 3;
+        assert_eq!(map[&(1, -1)], 5);
+
+        let set: FastSet<u64> = (0..1000).map(|i| i % 10).collect();
+        assert_eq!(set.len(), 10);
+    }
+
+    #[test]
+    fn marks_points() {
+        let mut visited = BitGrid::new(10, 7);
+
+        assert!(visited.insert(Point::new(3, 2)));
+        assert!(!visited.insert(Point::new(3, 2)));
+        assert!(visited.insert(Point::new(9, 6)));
+        assert!(visited.insert(Point::new(0, 0)));
+
+        assert!(visited.contains(Point::new(9, 6)));
+        assert!(!visited.contains(Point::new(2, 3)));
+        assert!(!visited.contains(Point::new(-1, 0)));
+        assert_eq!(visited.len(), 3);
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(3, 2), Point::new(9, 6)]
+        );
+
+        assert!(visited.remove(Point::new(3, 2)));
+        assert!(!visited.remove(Point::new(3, 2)));
+        visited.clear();
+        assert!(visited.is_empty());
+    }
+
+    #[test]
+    fn packs_tuples() {
+        let key = (-9_i16, 9, 0, -1);
+        assert_eq!(<(i16, i16, i16, i16)>::unpack(key.pack()), key);
+        assert_ne!(key.pack(), (9_i16, -9, 0, -1).pack());
+
+        assert_eq!((1_u8, 2).pack(), 0x0102);
+        assert_eq!((1_u16, 2, 3).pack(), 0x0001_0002_0003);
+        assert_eq!(<(i32, i32)>::unpack((-5_i32, 7).pack()), (-5, 7));
+        assert_eq!(<(i8, i8, i8)>::unpack((-1_i8, 0, 1).pack()), (-1, 0, 1));
+    }
+}
@@ -0,0 +1,213 @@
+//! Cycle detection for simulations that eventually repeat a state.
+//!
+//! A simulation is described by an initial state and a `step` function. Steps may return `None`
+//! when the simulation ends, e.g. when a guard leaves the map, in which case there is no cycle.
+use std::{collections::HashMap, hash::Hash};
+
+/// The states from `start` on repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the first state of the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the earliest step that has the same state as step `n`.
+    pub fn earliest_equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Applies `step` `n` times. Returns `None` if the simulation ended before.
+fn advance<S>(state: S, n: usize, step: &mut impl FnMut(&S) -> Option<S>) -> Option<S> {
+    (0..n).try_fold(state, |state, _| step(&state))
+}
+
+/// Finds the cycle the states run into once their start is known to be `length` steps apart.
+fn find_start<S: Clone + PartialEq>(
+    initial: S,
+    length: usize,
+    step: &mut impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut slow = initial.clone();
+    let mut fast = advance(initial, length, step)?;
+    let mut start = 0;
+
+    while slow != fast {
+        slow = step(&slow)?;
+        fast = step(&fast)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Finds the cycle with Floyd's tortoise and hare. Needs constant memory, but runs the
+/// simulation about three times up to the cycle.
+pub fn floyd<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut slow = step(&initial)?;
+    let mut fast = advance(initial.clone(), 2, &mut step)?;
+
+    while slow != fast {
+        slow = step(&slow)?;
+        fast = advance(fast, 2, &mut step)?;
+    }
+
+    // `fast` is a multiple of the cycle length ahead, so the length is the distance to the next meeting.
+    let mut length = 1;
+    let mut probe = step(&slow)?;
+    while probe != slow {
+        probe = step(&probe)?;
+        length += 1;
+    }
+
+    find_start(initial, length, &mut step)
+}
+
+/// Finds the cycle with Brent's algorithm. Needs constant memory and fewer steps than [`floyd`].
+pub fn brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut anchor = initial.clone();
+    let mut probe = step(&initial)?;
+
+    // search successive powers of two until the probe meets the anchor.
+    while anchor != probe {
+        if power == length {
+            anchor = probe.clone();
+            power *= 2;
+            length = 0;
+        }
+        probe = step(&probe)?;
+        length += 1;
+    }
+
+    find_start(initial, length, &mut step)
+}
+
+/// Finds the cycle by remembering a fingerprint of every state, which only has to be equal
+/// for equal states, e.g. a hash of the relevant part of a large state.
+/// Needs memory for every state before the cycle ends, but runs the simulation only once.
+pub fn find_cycle<S, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        if let Some(start) = seen.insert(fingerprint(&state), index) {
+            return Some(Cycle {
+                start,
+                length: index - start,
+            });
+        }
+        state = step(&state)?;
+    }
+
+    unreachable!()
+}
+
+/// Returns the state after `n` steps, skipping whole cycles once a state repeats.
+/// States are told apart by their `fingerprint`.
+pub fn state_after<S: Clone, K: Hash + Eq>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&S) -> S,
+    mut fingerprint: impl FnMut(&S) -> K,
+) -> S {
+    let mut seen = HashMap::new();
+    let mut states = vec![initial];
+
+    for index in 0..n {
+        let state = &states[index];
+        if let Some(start) = seen.insert(fingerprint(state), index) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return states.swap_remove(cycle.earliest_equivalent(n));
+        }
+        let next = step(state);
+        states.push(next);
+    }
+
+    states.swap_remove(n)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    /// Runs into a cycle of length 6 that starts after 3 steps:
+    /// 0 → 1 → 2 → 3 → 4 → 5 → 6 → 7 → 8 → 3 → …
+    fn step(&state: &u32) -> Option<u32> {
+        Some(if state == 8 { 3 } else { state + 1 })
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = Some(Cycle {
+            start: 3,
+            length: 6,
+        });
+
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_cycle(0, step, |&s| s), expected);
+        assert_eq!(brent(5, step).map(|c| (c.start, c.length)), Some((0, 6)));
+    }
+
+    #[test]
+    fn ends_without_cycle() {
+        let ending = |&state: &u32| (state < 10).then_some(state + 1);
+
+        assert_eq!(floyd(0, ending), None);
+        assert_eq!(brent(0, ending), None);
+        assert_eq!(find_cycle(0, ending, |&s| s), None);
+    }
+
+    #[test]
+    fn agrees_on_random_sequences() {
+        for modulus in [97_u64, 255, 1000, 4096] {
+            for seed in 0..20 {
+                let step = |&x: &u64| Some((x * x + 1) % modulus);
+                let cycle = find_cycle(seed, step, |&s| s);
+
+                assert!(cycle.is_some());
+                assert_eq!(floyd(seed, step), cycle);
+                assert_eq!(brent(seed, step), cycle);
+            }
+        }
+    }
+
+    #[test]
+    fn skips_ahead() {
+        let step = |s: &u32| step(s).unwrap();
+
+        assert_eq!(state_after(0, 2, step, |&s| s), 2);
+        assert_eq!(state_after(0, 9, step, |&s| s), 3);
+        assert_eq!(state_after(0, 1_000_000_000, step, |&s| s), 4);
+
+        let cycle = Cycle {
+            start: 3,
+            length: 6,
+        };
+        assert_eq!(cycle.earliest_equivalent(1_000_000_000), 4);
+        assert_eq!(cycle.earliest_equivalent(2), 2);
+    }
+}
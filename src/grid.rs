@@ -0,0 +1,206 @@
+//! A rectangular grid of cells, as most puzzle inputs are.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::point::{Direction, Point};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with all cells set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with the cells returned by `cell` for each point, line by line.
+    pub fn from_fn(width: usize, height: usize, cell: impl FnMut(Point) -> T) -> Self {
+        let points =
+            (0..height as i64).flat_map(|y| (0..width as i64).map(move |x| Point::new(x, y)));
+        Self {
+            width,
+            height,
+            cells: points.map(cell).collect(),
+        }
+    }
+
+    /// Converts every character of the lines of `input` to a cell.
+    /// Trailing empty lines are ignored, lines of different lengths are padded with `T::default()`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self
+    where
+        T: Default,
+    {
+        let lines: Vec<&str> = input.trim_end_matches(['\r', '\n']).lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for line in &lines {
+            let start = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            cells.resize_with(start + width, T::default);
+        }
+
+        Self {
+            width,
+            height: lines.len(),
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (0..self.width as i64).contains(&point.x) && (0..self.height as i64).contains(&point.y)
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    /// Returns the cell at `point`, or `None` if it is outside of the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Iterates over all points, line by line.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Iterates over all points and their cells, line by line.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// Returns the first point, line by line, whose cell is `value`.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, cell)| *cell == value).map(|(p, _)| p)
+    }
+
+    /// The orthogonal neighbors of `point` that are inside of the grid.
+    pub fn neighbors(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors_in(point, &Direction::ORTHOGONAL)
+    }
+
+    /// The neighbors of `point` in `directions` that are inside of the grid.
+    pub fn neighbors_in<'a>(
+        &'a self,
+        point: Point,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |d| point + d.vector())
+            .filter(|p| self.contains(*p))
+    }
+
+    /// Converts every cell, keeping the positions.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+/// Prints one line per row, with the cells next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_grids() {
+        let grid = Grid::parse("#.\n.#.\n\n", |c| c);
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(1, 1)], '#');
+        assert_eq!(grid[Point::new(2, 0)], '\0');
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.find(&'#'), Some(Point::new(0, 0)));
+        assert_eq!(grid.map(|&c| c == '#').find(&false), Some(Point::new(1, 0)));
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+
+        assert_eq!(
+            grid.neighbors(Point::new(0, 0)).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors(Point::new(1, 1)).count(), 3);
+        assert_eq!(
+            grid.neighbors_in(Point::new(1, 1), &Direction::ALL).count(),
+            5
+        );
+    }
+
+    #[test]
+    fn displays_grids() {
+        let mut grid = Grid::new(3, 2, '.');
+        grid[Point::new(2, 1)] = '#';
+
+        assert_eq!(grid.to_string(), "...\n..#\n");
+        assert_eq!(grid.points().last(), Some(Point::new(2, 1)));
+    }
+}
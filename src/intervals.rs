@@ -0,0 +1,342 @@
+//! Sets of disjoint ranges, and an allocator that hands out free spans first-fit,
+//! e.g. to compact the disk of 2024 day 9.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+/// A set of values that is stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    /// The end of every span, by its start.
+    spans: BTreeMap<T, T>,
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            spans: BTreeMap::new(),
+        }
+    }
+
+    /// Adds all values of `range`, merging it with the spans it overlaps or touches.
+    /// Returns the span that contains `range` afterwards.
+    pub fn insert(&mut self, range: Range<T>) -> Range<T> {
+        if range.is_empty() {
+            return range;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        let touching: Vec<(T, T)> = self
+            .spans
+            .range(..=end)
+            .rev()
+            .take_while(|(_, &e)| e >= start)
+            .map(|(&s, &e)| (s, e))
+            .collect();
+
+        for (s, e) in touching {
+            self.spans.remove(&s);
+            start = start.min(s);
+            end = end.max(e);
+        }
+
+        self.spans.insert(start, end);
+        start..end
+    }
+
+    /// Removes all values of `range`, splitting the spans it cuts.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        for span in self.overlapping(range.clone()).collect::<Vec<_>>() {
+            self.spans.remove(&span.start);
+            if span.start < range.start {
+                self.spans.insert(span.start, range.start);
+            }
+            if range.end < span.end {
+                self.spans.insert(range.end, span.end);
+            }
+        }
+    }
+
+    /// Returns the span that contains `value`.
+    pub fn span_containing(&self, value: T) -> Option<Range<T>> {
+        self.spans
+            .range(..=value)
+            .next_back()
+            .filter(|(_, &end)| value < end)
+            .map(|(&start, &end)| start..end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.span_containing(value).is_some()
+    }
+
+    /// Iterates over the spans that share at least one value with `range`, in order.
+    pub fn overlapping(&self, range: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        let first = self
+            .span_containing(range.start)
+            .map_or(range.start, |span| span.start);
+
+        self.spans
+            .range(first..)
+            .take_while(move |(&start, _)| start < range.end)
+            .filter(move |_| !range.is_empty())
+            .map(|(&start, &end)| start..end)
+    }
+
+    /// Iterates over all spans, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.spans.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The number of spans.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+impl<T: Ord + Copy> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        ranges.into_iter().for_each(|range| {
+            set.insert(range);
+        });
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Free space on a line of cells, handed out first-fit: a request is served from the free span
+/// with the lowest start that is large enough.
+///
+/// Free spans are additionally grouped in size classes: one per length below 16, and one per power
+/// of two above. Every span in a larger class than the request fits, so those classes are answered
+/// by their first span. Only the class of a request of 16 cells or more is scanned, and only up to
+/// the best span of the larger classes.
+#[derive(Debug, Clone, Default)]
+pub struct SpanAllocator {
+    free: IntervalSet<usize>,
+    /// The starts of the free spans, by their [`size_class`].
+    classes: Vec<BTreeSet<usize>>,
+}
+
+/// Lengths up to this one have a size class of their own.
+const EXACT_CLASSES: usize = 16;
+
+fn size_class(length: usize) -> usize {
+    if length < EXACT_CLASSES {
+        length
+    } else {
+        // 16 has the class 16, 32 the class 17 and so on.
+        EXACT_CLASSES + (length.ilog2() - EXACT_CLASSES.ilog2()) as usize
+    }
+}
+
+impl SpanAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_span(&mut self, span: Range<usize>) {
+        let class = size_class(span.len());
+        if self.classes.len() <= class {
+            self.classes.resize_with(class + 1, BTreeSet::new);
+        }
+        self.classes[class].insert(span.start);
+    }
+
+    fn remove_span(&mut self, span: &Range<usize>) {
+        self.classes[size_class(span.len())].remove(&span.start);
+    }
+
+    /// Marks `range` as free, merging it with adjacent free spans.
+    pub fn release(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let before = range.start.checked_sub(1).unwrap_or(range.start);
+        let touching: Vec<_> = self.free.overlapping(before..range.end + 1).collect();
+        touching.iter().for_each(|span| self.remove_span(span));
+
+        let merged = self.free.insert(range);
+        self.add_span(merged);
+    }
+
+    /// Returns the start of the first free span that can hold `size` cells, without taking it.
+    pub fn first_fit(&self, size: usize) -> Option<usize> {
+        if size == 0 {
+            return Some(0);
+        }
+
+        let class = size_class(size);
+        let larger = self
+            .classes
+            .iter()
+            .skip(class + 1)
+            .filter_map(|starts| starts.first().copied())
+            .min();
+
+        let same = self.classes.get(class).and_then(|starts| {
+            starts
+                .iter()
+                .take_while(|&&start| larger.is_none_or(|l| start < l))
+                .find(|&&start| {
+                    self.free
+                        .span_containing(start)
+                        .is_some_and(|s| s.len() >= size)
+                })
+                .copied()
+        });
+
+        same.or(larger)
+    }
+
+    /// Marks `range` as used. It has to lie within a single free span.
+    pub fn take(&mut self, range: Range<usize>) -> bool {
+        let Some(span) = self.free.span_containing(range.start) else {
+            return false;
+        };
+        if range.end > span.end {
+            return false;
+        }
+
+        self.remove_span(&span);
+        self.free.remove(range.clone());
+        for rest in [span.start..range.start, range.end..span.end] {
+            if !rest.is_empty() {
+                self.add_span(rest);
+            }
+        }
+        true
+    }
+
+    /// Takes `size` cells from the first free span that is large enough.
+    pub fn allocate(&mut self, size: usize) -> Option<Range<usize>> {
+        let start = self.first_fit(size)?;
+        self.take(start..start + size);
+        Some(start..start + size)
+    }
+
+    /// The free spans, in order.
+    pub fn free_spans(&self) -> &IntervalSet<usize> {
+        &self.free
+    }
+}
+
+impl FromIterator<Range<usize>> for SpanAllocator {
+    fn from_iter<I: IntoIterator<Item = Range<usize>>>(free: I) -> Self {
+        let mut allocator = Self::new();
+        free.into_iter().for_each(|range| allocator.release(range));
+        allocator
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_and_splits_intervals() {
+        let mut set: IntervalSet<i32> = [1..3, 5..7, 3..4].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..4, 5..7]);
+
+        assert_eq!(set.insert(4..5), 1..7);
+        assert_eq!(set.len(), 1);
+
+        set.remove(2..3);
+        set.remove(6..10);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..2, 3..6]);
+        assert!(set.contains(3) && !set.contains(2) && !set.contains(6));
+        assert_eq!(set.span_containing(4), Some(3..6));
+    }
+
+    #[test]
+    fn queries_ranges() {
+        let set: IntervalSet<i32> = [0..2, 4..6, 8..10].into_iter().collect();
+
+        assert_eq!(set.overlapping(1..5).collect::<Vec<_>>(), [0..2, 4..6]);
+        assert_eq!(set.overlapping(2..4).count(), 0);
+        assert_eq!(set.overlapping(9..20).collect::<Vec<_>>(), [8..10]);
+        assert_eq!(set.overlapping(5..5).count(), 0);
+    }
+
+    #[test]
+    fn allocates_first_fit() {
+        let mut allocator: SpanAllocator = [0..2, 5..9, 12..20].into_iter().collect();
+
+        assert_eq!(allocator.first_fit(3), Some(5));
+        assert_eq!(allocator.allocate(3), Some(5..8));
+        assert_eq!(allocator.allocate(2), Some(0..2));
+        assert_eq!(allocator.allocate(2), Some(12..14));
+        assert_eq!(allocator.allocate(1), Some(8..9));
+        assert_eq!(allocator.allocate(7), None);
+
+        allocator.release(8..14);
+        assert_eq!(allocator.free_spans().iter().collect::<Vec<_>>(), [8..20]);
+        assert_eq!(allocator.allocate(7), Some(8..15));
+        assert!(!allocator.take(0..1));
+    }
+
+    #[test]
+    fn matches_linear_scan() {
+        // compares with a list of cells on a deterministic sequence of requests,
+        // with sizes in the exact and in the scanned size classes.
+        let mut state = 0x2545_f491_u64;
+        let mut random = |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound) as usize
+        };
+
+        let mut allocator = SpanAllocator::new();
+        let mut cells = vec![false; 2000];
+        for start in (0..2000).step_by(50) {
+            let length = 1 + random(45);
+            allocator.release(start..start + length);
+            cells[start..start + length].fill(true);
+        }
+
+        for _ in 0..500 {
+            let size = 1 + random(40);
+            // first fit starts at the beginning of a free span.
+            let span_start = cells
+                .iter()
+                .enumerate()
+                .filter(|&(s, &free)| free && (s == 0 || !cells[s - 1]))
+                .map(|(s, _)| s)
+                .find(|&s| cells[s..].iter().take_while(|&&c| c).count() >= size);
+
+            let allocated = allocator.allocate(size);
+            assert_eq!(allocated.as_ref().map(|r| r.start), span_start);
+            if let Some(range) = allocated {
+                cells[range].fill(false);
+            }
+
+            if random(3) == 0 {
+                let start = random(1950);
+                let length = 1 + random(50);
+                allocator.release(start..start + length);
+                cells[start..start + length].fill(true);
+            }
+        }
+    }
+}
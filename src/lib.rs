@@ -1,3 +1,14 @@
+pub mod collections;
+pub mod cycles;
+pub mod grid;
+pub mod intervals;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod regions;
 pub mod template;
+pub mod trie;
+pub mod vm;
 
 // Use this file to add helper functions and additional modules.
@@ -1,18 +1,21 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, inputs, leaderboard, read, scaffold, solve, stars, stats, status, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::inputs::Action;
+    use advent_of_code::template::{Day, DaySelection, Scoring};
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
+            offline: bool,
         },
         Read {
             day: Day,
@@ -21,44 +24,93 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            store: bool,
+            verbose: bool,
             submit: Option<u8>,
         },
         All {
+            days: Option<DaySelection>,
             release: bool,
+            jobs: usize,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySelection>,
             store: bool,
         },
+        Verify {
+            days: Option<DaySelection>,
+            release: bool,
+            jobs: usize,
+        },
+        Status {
+            markdown: bool,
+        },
+        Stars {
+            leaderboard: Option<String>,
+            user: Option<String>,
+        },
+        Stats,
+        Leaderboard {
+            path: String,
+            scoring: Scoring,
+            day: Option<Day>,
+            fetch: Option<String>,
+        },
+        Inputs {
+            action: Action,
+        },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let release = args.contains("--release");
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                AppArguments::All {
+                    days: args.opt_free_from_str()?,
+                    release,
+                    jobs,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: args.opt_free_from_str()?,
                     store,
                 }
             }
+            Some("verify") => {
+                let release = args.contains("--release");
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                AppArguments::Verify {
+                    days: args.opt_free_from_str()?,
+                    release,
+                    jobs,
+                }
+            }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
+                offline: args.contains("--offline"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
@@ -67,15 +119,46 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                store: args.contains("--store"),
+                verbose: args.contains("--verbose"),
+            },
+            Some("status") => AppArguments::Status {
+                markdown: args.contains("--markdown"),
+            },
+            Some("stars") => AppArguments::Stars {
+                leaderboard: args.opt_value_from_str("--leaderboard")?,
+                user: args.opt_value_from_str("--user")?,
+            },
+            Some("stats") => AppArguments::Stats,
+            Some("leaderboard") => {
+                let scoring = args
+                    .opt_value_from_str("--scoring")?
+                    .unwrap_or(Scoring::Local);
+                let day = args.opt_value_from_str("--day")?;
+                let fetch = args.opt_value_from_str("--fetch")?;
+
+                AppArguments::Leaderboard {
+                    path: args.free_from_str()?,
+                    scoring,
+                    day,
+                    fetch,
+                }
+            }
+            Some("inputs") => AppArguments::Inputs {
+                action: args.free_from_str()?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,39 +185,55 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                days,
+                release,
+                jobs,
+            } => all::handle(days, release, jobs),
+            AppArguments::Time { days, all, store } => time::handle(days, all, store),
+            AppArguments::Verify {
+                days,
+                release,
+                jobs,
+            } => verify::handle(days, release, jobs),
+            AppArguments::Download {
+                day,
+                force,
+                offline,
+            } => download::handle(day, force, offline),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                dry_run,
+                template,
+            } => {
+                scaffold::handle(day, overwrite, dry_run, template.as_deref());
+                if download && !dry_run {
+                    download::handle(day, false, false);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                store,
+                verbose,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, release, dhat, store, verbose, submit),
+            AppArguments::Status { markdown } => status::handle(markdown),
+            AppArguments::Stars { leaderboard, user } => stars::handle(leaderboard, user),
+            AppArguments::Stats => stats::handle(),
+            AppArguments::Leaderboard {
+                path,
+                scoring,
+                day,
+                fetch,
+            } => leaderboard::handle(&path, scoring, day, fetch),
+            AppArguments::Inputs { action } => inputs::handle(action),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
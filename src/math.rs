@@ -0,0 +1,351 @@
+//! Integer math that comes up in puzzles: linear systems, modular arithmetic and digits.
+use std::iter;
+
+use num::{rational::Ratio, Integer, Zero};
+
+/// An exact fraction, used while solving linear systems.
+pub type Rational = Ratio<i128>;
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the non-negative
+/// greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (i128::from(a), i128::from(b));
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    // the coefficients are bounded by the inputs, only the gcd of `i64::MIN` does not fit.
+    let narrow = |value: i128| i64::try_from(value).unwrap_or(i64::MIN);
+    (narrow(old_r), narrow(old_x), narrow(old_y))
+}
+
+/// Returns the inverse of `a` modulo `modulus` in `0..modulus`,
+/// or `None` if `a` and `modulus` are not coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (gcd, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the chinese remainder theorem.
+///
+/// Returns the smallest non-negative solution together with the least common multiple of the
+/// moduli, every solution is congruent to it. The moduli do not have to be coprime, `None` is
+/// returned if the congruences contradict each other or the result does not fit into an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter().try_fold(
+        (0, 1),
+        |(residue, modulus), (next_residue, next_modulus)| {
+            if next_modulus <= 0 {
+                return None;
+            }
+
+            let (gcd, inverse, _) = extended_gcd(modulus, next_modulus);
+            let difference =
+                i128::from(next_residue.rem_euclid(next_modulus)) - i128::from(residue);
+            if difference % i128::from(gcd) != 0 {
+                return None;
+            }
+
+            let step = i128::from(next_modulus / gcd);
+            let k = (difference / i128::from(gcd) % step * i128::from(inverse)).rem_euclid(step);
+            let lcm = i128::from(modulus) * step;
+            let residue = (i128::from(residue) + i128::from(modulus) * k).rem_euclid(lcm);
+
+            Some((i64::try_from(residue).ok()?, i64::try_from(lcm).ok()?))
+        },
+    )
+}
+
+/// Least common multiple of all values, `1` for no values.
+pub fn lcm_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::one(), |lcm, value| lcm.lcm(&value))
+}
+
+/// Greatest common divisor of all values, `0` for no values.
+pub fn gcd_all<T: Integer + Copy>(values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::zero(), |gcd, value| gcd.gcd(&value))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Solves `matrix * [x, y] = rhs` with Cramer's rule. Returns `None` if the system has no
+/// unique solution or the solution is not integral.
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> Option<[i64; 2]> {
+    let [[a, b], [c, d]] = matrix.map(|row| row.map(i128::from));
+    let [e, f] = rhs.map(i128::from);
+
+    let det = a * d - b * c;
+    if det == 0 {
+        return None;
+    }
+
+    let x = e * d - b * f;
+    let y = a * f - e * c;
+    if x % det != 0 || y % det != 0 {
+        return None;
+    }
+
+    Some([i64::try_from(x / det).ok()?, i64::try_from(y / det).ok()?])
+}
+
+/// Solves the square system `matrix * x = rhs` exactly with gaussian elimination.
+/// Returns `None` if the system has no unique solution.
+pub fn solve_linear(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<Vec<Rational>> {
+    let n = rhs.len();
+    if matrix.len() != n || matrix.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    // augmented matrix with the right hand side as last column.
+    let mut rows: Vec<Vec<Rational>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, value)| {
+            row.iter()
+                .chain(iter::once(value))
+                .map(|&v| Rational::from_integer(v.into()))
+                .collect()
+        })
+        .collect();
+
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| !rows[row][column].is_zero())?;
+        rows.swap(column, pivot);
+
+        let pivot_row = rows[column].clone();
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index == column || row[column].is_zero() {
+                continue;
+            }
+
+            let factor = row[column] / pivot_row[column];
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(column) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    Some((0..n).map(|row| rows[row][n] / rows[row][row]).collect())
+}
+
+/// Like [`solve_linear`], but only returns solutions where every value is an integer.
+pub fn solve_linear_integer(matrix: &[Vec<i64>], rhs: &[i64]) -> Option<Vec<i64>> {
+    solve_linear(matrix, rhs)?
+        .into_iter()
+        .map(|value| {
+            if value.is_integer() {
+                i64::try_from(value.to_integer()).ok()
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of decimal digits of `value`, `0` has one digit.
+pub fn digit_count(value: u64) -> u32 {
+    value.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// Splits a number with an even number of digits into its upper and lower half,
+/// e.g. `1234` into `(12, 34)`. Returns `None` for an odd number of digits.
+pub fn split_digits(value: u64) -> Option<(u64, u64)> {
+    let count = digit_count(value);
+    if !count.is_multiple_of(2) {
+        return None;
+    }
+
+    let divisor = 10_u64.pow(count / 2);
+    Some((value / divisor, value % divisor))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use num::Integer;
+
+    use super::*;
+
+    /// Deterministic xorshift generator, so that the properties are checked for many inputs.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn range(&mut self, min: i64, max: i64) -> i64 {
+            min + (self.next() % (max - min + 1).unsigned_abs()) as i64
+        }
+    }
+
+    const CASES: usize = 1000;
+
+    #[test]
+    fn extended_gcd_properties() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..CASES {
+            let (a, b) = (
+                rng.range(-1_000_000, 1_000_000),
+                rng.range(-1_000_000, 1_000_000),
+            );
+            let (gcd, x, y) = extended_gcd(a, b);
+
+            assert_eq!(gcd, a.gcd(&b));
+            assert_eq!(a * x + b * y, gcd);
+        }
+    }
+
+    #[test]
+    fn mod_inverse_properties() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..CASES {
+            let (a, modulus) = (rng.range(-10_000, 10_000), rng.range(2, 10_000));
+
+            match mod_inverse(a, modulus) {
+                Some(inverse) => {
+                    assert!((0..modulus).contains(&inverse));
+                    assert_eq!((a * inverse).rem_euclid(modulus), 1);
+                }
+                None => assert_ne!(a.gcd(&modulus), 1),
+            }
+        }
+
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(2, 0), None);
+    }
+
+    #[test]
+    fn crt_properties() {
+        let mut rng = Rng(0xdead_beef_cafe_babe);
+
+        for _ in 0..CASES {
+            let moduli: Vec<i64> = (0..rng.range(1, 4)).map(|_| rng.range(1, 1000)).collect();
+            let lcm = lcm_all(moduli.iter().copied());
+            let x = rng.range(0, lcm - 1);
+
+            let congruences = moduli.iter().map(|&m| (x % m + m * rng.range(-2, 2), m));
+            assert_eq!(crt(congruences), Some((x, lcm)));
+        }
+
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn lcm_and_gcd_of_iterators() {
+        assert_eq!(lcm_all([101, 103]), 10403);
+        assert_eq!(lcm_all([4_u8, 6, 10]), 60);
+        assert_eq!(gcd_all([12, -18, 30]), 6);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+    }
+
+    #[test]
+    fn solve_2x2_properties() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+
+        for _ in 0..CASES {
+            let matrix = [[0; 2]; 2].map(|row| row.map(|_| rng.range(-100, 100)));
+            let solution = [rng.range(-1000, 1000), rng.range(-1000, 1000)];
+            let rhs = matrix.map(|row| row[0] * solution[0] + row[1] * solution[1]);
+
+            let det = matrix[0][0] * matrix[1][1] - matrix[0][1] * matrix[1][0];
+            if det != 0 {
+                assert_eq!(solve_2x2(matrix, rhs), Some(solution));
+            } else {
+                assert_eq!(solve_2x2(matrix, rhs), None);
+            }
+        }
+
+        // the first claw machine of the day 13 example.
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some([80, 40])
+        );
+        assert_eq!(solve_2x2([[26, 67], [66, 21]], [12748, 12176]), None);
+    }
+
+    #[test]
+    fn solve_linear_properties() {
+        let mut rng = Rng(0x0f0f_0f0f_1234_5678);
+
+        for n in 1..=4 {
+            for _ in 0..CASES / 10 {
+                let matrix: Vec<Vec<i64>> = (0..n)
+                    .map(|_| (0..n).map(|_| rng.range(-20, 20)).collect())
+                    .collect();
+                let solution: Vec<i64> = (0..n).map(|_| rng.range(-100, 100)).collect();
+                let rhs: Vec<i64> = matrix
+                    .iter()
+                    .map(|row| row.iter().zip(&solution).map(|(a, x)| a * x).sum())
+                    .collect();
+
+                match solve_linear_integer(&matrix, &rhs) {
+                    Some(result) => assert_eq!(result, solution),
+                    None => assert_eq!(solve_linear(&matrix, &rhs), None),
+                }
+            }
+        }
+
+        assert_eq!(
+            solve_linear(&[vec![2, 0], vec![0, 4]], &[1, 2]),
+            Some(vec![Rational::new(1, 2), Rational::new(1, 2)])
+        );
+        assert_eq!(
+            solve_linear_integer(&[vec![2, 0], vec![0, 4]], &[1, 2]),
+            None
+        );
+        assert_eq!(solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]), None);
+    }
+
+    #[test]
+    fn digit_properties() {
+        let mut rng = Rng(0x1357_9bdf_2468_ace0);
+
+        for _ in 0..CASES {
+            let value = rng.next() >> rng.range(0, 63);
+            let digits = value.to_string();
+
+            assert_eq!(digit_count(value) as usize, digits.len());
+            match split_digits(value) {
+                Some((upper, lower)) => {
+                    let (upper_digits, lower_digits) = digits.split_at(digits.len() / 2);
+                    assert_eq!(upper, upper_digits.parse().unwrap());
+                    assert_eq!(lower, lower_digits.parse().unwrap());
+                }
+                None => assert!(digits.len() % 2 == 1),
+            }
+        }
+
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(split_digits(1000), Some((10, 0)));
+        assert_eq!(split_digits(253_000), Some((253, 0)));
+    }
+}
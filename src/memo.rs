@@ -0,0 +1,348 @@
+//! Memoization for recursive solutions, with hit and miss statistics.
+//!
+//! The statistics of named caches are collected globally, so that `cargo solve <day> --verbose`
+//! can print them below the result of each part.
+use std::{
+    fmt::Display,
+    hash::Hash,
+    ops::{Add, Sub},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::collections::FastMap;
+
+/// How often a cache was asked for a value, and how often the value was already known.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// The share of lookups that were answered from the cache, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Add for MemoStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+        }
+    }
+}
+
+impl Sub for MemoStats {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            hits: self.hits - other.hits,
+            misses: self.misses - other.misses,
+        }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+static REPORTS: Mutex<Vec<(&'static str, MemoStats)>> = Mutex::new(Vec::new());
+
+/// Adds `stats` to the global statistics of the cache called `name`.
+fn record(name: &'static str, stats: MemoStats) {
+    let mut reports = REPORTS.lock().unwrap_or_else(|e| e.into_inner());
+    match reports.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, total)) => *total = *total + stats,
+        None => reports.push((name, stats)),
+    }
+}
+
+/// Returns the statistics that named caches reported since the last call, in order of their first report.
+pub fn take_stats() -> Vec<(&'static str, MemoStats)> {
+    let mut reports = REPORTS.lock().unwrap_or_else(|e| e.into_inner());
+    std::mem::take(&mut *reports)
+}
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Empties the caches of all [`memoize!`](crate::memoize) functions, on every thread. A cache is
+/// emptied lazily, by the next outermost call of its function.
+///
+/// The runner calls this before every sample, so that benchmarks do not measure a warm cache.
+pub fn clear_caches() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts the calls of [`clear_caches`], so that the caches can tell whether they are outdated.
+#[doc(hidden)]
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A cache of computed values that can be passed down through recursive calls.
+///
+/// ```ignore
+/// let mut memo = Memo::named("stones");
+/// fn count(stone: u64, blinks: u32, memo: &mut Memo<(u64, u32), u64>) -> u64 {
+///     memo.get_or_compute((stone, blinks), |memo| /* ...recursive calls with memo... */)
+/// }
+/// ```
+pub struct Memo<K, V> {
+    cache: FastMap<K, V>,
+    name: Option<&'static str>,
+    stats: MemoStats,
+    reported: MemoStats,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self {
+            cache: FastMap::default(),
+            name: None,
+            stats: MemoStats::default(),
+            reported: MemoStats::default(),
+        }
+    }
+
+    /// Creates a cache whose statistics are reported under `name` when it is dropped.
+    pub fn named(name: &'static str) -> Self {
+        let mut memo = Self::new();
+        memo.name = Some(name);
+        memo
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    /// `compute` receives the cache again, so that it can recurse.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.lookup(&key) {
+            return value;
+        }
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value for `key`, counting the lookup as a hit or a miss.
+    pub fn lookup(&mut self, key: &K) -> Option<V> {
+        let value = self.cache.get(key).cloned();
+        match value {
+            Some(_) => self.stats.hits += 1,
+            None => self.stats.misses += 1,
+        }
+        value
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    /// Removes all cached values, the statistics are kept.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+}
+
+impl<K, V> Memo<K, V> {
+    /// Adds the lookups since the last report to the global statistics, if the cache is named.
+    pub fn report(&mut self) {
+        if let Some(name) = self.name {
+            if self.stats != self.reported {
+                record(name, self.stats - self.reported);
+                self.reported = self.stats;
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        self.report();
+    }
+}
+
+/// Memoizes a free function, like an attribute on its definition would. The arguments form the key
+/// of a cache that lives as long as the thread, so recursive calls are simply calls of the function.
+///
+/// Arguments have to be `Clone + Hash + Eq` and the result `Clone`. Lookups are reported under the
+/// name of the function once the outermost call returns. The cache is kept between outermost
+/// calls until [`memo::clear_caches`](crate::memo::clear_caches) is called.
+///
+/// ```ignore
+/// advent_of_code::memoize! {
+///     fn stones(stone: u64, blinks: u32) -> u64 {
+///         if blinks == 0 { 1 } else { stones(stone * 2024, blinks - 1) }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! memoize {
+    (
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block
+    ) => {
+        $(#[$attr])*
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            ::std::thread_local! {
+                static MEMO: ::std::cell::RefCell<$crate::memo::Memo<($($ty,)*), $ret>> =
+                    ::std::cell::RefCell::new($crate::memo::Memo::named(stringify!($name)));
+                static DEPTH: ::std::cell::Cell<usize> = const { ::std::cell::Cell::new(0) };
+                static GENERATION: ::std::cell::Cell<u64> = const { ::std::cell::Cell::new(0) };
+            }
+
+            if DEPTH.get() == 0 && GENERATION.get() != $crate::memo::generation() {
+                GENERATION.set($crate::memo::generation());
+                MEMO.with_borrow_mut(|memo| memo.clear());
+            }
+
+            let key = ($(::std::clone::Clone::clone(&$arg),)*);
+            let cached = MEMO.with_borrow_mut(|memo| memo.lookup(&key));
+            let value = match cached {
+                Some(value) => value,
+                None => {
+                    DEPTH.set(DEPTH.get() + 1);
+                    #[allow(clippy::redundant_closure_call)]
+                    let value = (move || -> $ret { $body })();
+                    DEPTH.set(DEPTH.get() - 1);
+                    MEMO.with_borrow_mut(|memo| memo.insert(key, ::std::clone::Clone::clone(&value)));
+                    value
+                }
+            };
+
+            if DEPTH.get() == 0 {
+                MEMO.with_borrow_mut(|memo| memo.report());
+            }
+            value
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fibonacci(n - 1, memo) + fibonacci(n - 2, memo)
+            }
+        })
+    }
+
+    crate::memoize! {
+        fn paths(width: usize, height: usize) -> u64 {
+            if width == 0 || height == 0 {
+                1
+            } else {
+                paths(width - 1, height) + paths(width, height - 1)
+            }
+        }
+    }
+
+    #[test]
+    fn memoizes_recursion() {
+        let mut memo = Memo::new();
+
+        assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 88,
+                misses: 91
+            }
+        );
+
+        assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn reports_statistics() {
+        // the reports are global, so both kinds of caches are checked in the same test.
+        let find = |name| {
+            take_stats()
+                .into_iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, stats)| stats)
+        };
+
+        {
+            let mut memo = Memo::named("fibonacci");
+            fibonacci(10, &mut memo);
+        }
+        let stats = find("fibonacci").unwrap();
+        assert_eq!(
+            stats,
+            MemoStats {
+                hits: 8,
+                misses: 11
+            }
+        );
+        assert_eq!(stats.to_string(), "8 hits, 11 misses (42.1% hit rate)");
+
+        assert_eq!(paths(16, 16), 601_080_390);
+        // every grid size up to 16x16 is computed once, except for the empty one.
+        assert_eq!(find("paths").map(|s| s.misses), Some(17 * 17 - 1));
+
+        assert_eq!(paths(10, 3), 286);
+        assert_eq!(find("paths"), Some(MemoStats { hits: 1, misses: 0 }));
+
+        clear_caches();
+        assert_eq!(paths(10, 3), 286);
+        // 11x4 grid sizes without the empty one, 2 lookups for each of the 10x3 non-empty ones.
+        assert_eq!(
+            find("paths"),
+            Some(MemoStats {
+                hits: 10 * 3 * 2 + 1 - 43,
+                misses: 43
+            })
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Parsers and helpers that are shared by the solutions.
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, one_of, space0},
+    combinator::{map_res, opt, recognize},
+    multi::separated_list0,
+    sequence::{delimited, pair, separated_pair},
+    IResult, Parser,
+};
+
+/// Parses an integer without a sign into any integer type, e.g. `42`.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parses an integer with an optional sign into any integer type, e.g. `-42` or `+42`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parses a pair of comma separated integers, e.g. `3,-4` or `3, -4`.
+pub fn coordinates<T: FromStr>(input: &str) -> IResult<&str, (T, T)> {
+    separated_pair(signed, delimited(space0, char(','), space0), signed)(input)
+}
+
+/// Parses a key and a value around `separator`, ignoring the spaces around it,
+/// e.g. `Register A: 729` with a `":"` separator.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, nom::error::Error<&'a str>>,
+    separator: &'a str,
+    value: impl Parser<&'a str, V, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, delimited(space0, tag(separator), space0), value)
+}
+
+/// Applies `parser` to every line. Stops before the first line that can not be parsed,
+/// so that trailing newlines are left to the caller.
+pub fn lines_of<'a, O>(
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list0(line_ending, parser)
+}
+
+/// Extracts all integers of a line, ignoring everything in between, e.g. `p=0,4 v=3,-3`.
+///
+/// A `-` directly in front of a number is treated as a sign, unless it follows another number
+/// like in the range `1-3`. Signs are dropped for unsigned types.
+pub fn integers<T: FromStr>(line: &str) -> Vec<T> {
+    let bytes = line.as_bytes();
+    let mut numbers = Vec::new();
+    let mut end = 0;
+
+    while end < bytes.len() {
+        if !bytes[end].is_ascii_digit() {
+            end += 1;
+            continue;
+        }
+
+        let start = end;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+
+        let negative = start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit());
+        let sign = if negative { start - 1 } else { start };
+
+        if let Some(number) = line[sign..end]
+            .parse()
+            .ok()
+            .or_else(|| line[start..end].parse().ok())
+        {
+            numbers.push(number);
+        }
+    }
+
+    numbers
+}
+
+/// Splits the input into blocks of lines that are separated by blank lines.
+pub fn blocks<'a>(input: &'a str) -> Vec<&'a str> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+
+    let mut push_block = |block: &'a str| {
+        let block = block.trim_end_matches(['\r', '\n']);
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    };
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            push_block(&input[start..offset]);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+
+    push_block(&input[start..]);
+    blocks
+}
+
+/// Applies `parser` to the whole input. Unlike calling the parser directly, this fails
+/// if anything but whitespace is left over, and the error points to the offending position.
+pub fn parse_all<'a, O>(
+    mut parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+    input: &'a str,
+) -> Result<O, ParseError> {
+    match parser.parse(input) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(ParseError::new(
+            input,
+            rest.trim_start(),
+            "unconsumed input".into(),
+        )),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(
+            input,
+            e.input,
+            format!("expected {}", e.code.description()),
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(input, "", "incomplete input".into())),
+    }
+}
+
+/// Like [`parse_all`], but prints the error to stderr and returns `None`, so that a solution can
+/// bail out with `?`, e.g. `let rules = parse_or_report(parse, input)?;`.
+pub fn parse_or_report<'a, O>(
+    parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+    input: &'a str,
+) -> Option<O> {
+    parse_all(parser, input)
+        .inspect_err(|e| eprintln!("{e}"))
+        .ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error returned by [`parse_all`], pointing to the position where parsing stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1.
+    pub line: usize,
+    /// Column of the error in characters, starting at 1.
+    pub column: usize,
+    /// Contents of the line of the error.
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// Creates an error at the position of `rest`, which has to be a suffix of `input`.
+    fn new(input: &str, rest: &str, reason: String) -> Self {
+        let offset = input.len() - rest.len();
+        let (before, _) = input.split_at(offset);
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            reason,
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "failed to parse line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {}^", " ".repeat(self.column - 1))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::alpha1, sequence::preceded};
+
+    use super::*;
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(unsigned::<u8>("42 rest"), Ok((" rest", 42)));
+        assert!(unsigned::<u8>("-42").is_err());
+        assert!(unsigned::<u8>("256").is_err());
+        assert_eq!(signed::<i64>("-42"), Ok(("", -42)));
+        assert_eq!(signed::<i16>("+42"), Ok(("", 42)));
+        assert_eq!(coordinates::<i32>("3, -4"), Ok(("", (3, -4))));
+    }
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(integers::<i32>("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+        assert_eq!(integers::<u32>("p=0,4 v=3,-3"), vec![0, 4, 3, 3]);
+        assert_eq!(integers::<i32>("1-3 a: abc"), vec![1, 3]);
+        assert!(integers::<i32>("no numbers").is_empty());
+    }
+
+    #[test]
+    fn splits_blocks() {
+        assert_eq!(blocks("a\nb\n\nc\r\n\r\n\n\nd\n"), vec!["a\nb", "c", "d"]);
+        assert_eq!(blocks("\n\na"), vec!["a"]);
+        assert!(blocks("").is_empty());
+    }
+
+    #[test]
+    fn parses_lines() {
+        let register = key_value(preceded(tag("Register "), alpha1), ":", unsigned::<u64>);
+        assert_eq!(
+            parse_all(lines_of(register), "Register A: 729\nRegister B : 0\n"),
+            Ok(vec![("A", 729), ("B", 0)])
+        );
+    }
+
+    #[test]
+    fn reports_error_position() {
+        let error = parse_all(lines_of(coordinates::<u8>), "1,2\n3,x\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "3,x");
+        assert_eq!(error.reason, "unconsumed input");
+
+        let error = parse_all(preceded(tag("p="), coordinates::<u8>), "v=1,2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(
+            error.to_string(),
+            "failed to parse line 1, column 1: expected Tag\n  v=1,2\n  ^"
+        );
+    }
+}
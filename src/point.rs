@@ -0,0 +1,295 @@
+//! Points, vectors and directions on a grid. `x` grows to the right and `y` grows downwards,
+//! like the lines and columns of the puzzle input.
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A position on a grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+/// The difference between two [`Point`]s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+/// The absolute difference that also works for unsigned types.
+fn distance<T: Copy + PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The number of orthogonal steps between both points.
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// The number of steps between both points if diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        let (dx, dy) = (distance(self.x, other.x), distance(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, vector: Vector<T>) -> Self {
+        Self::new(self.x + vector.x, self.y + vector.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, vector: Vector<T>) -> Self {
+        Self::new(self.x - vector.x, self.y - vector.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Self) -> Vector<T> {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, vector: Vector<T>) {
+        self.x += vector.x;
+        self.y += vector.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign<Vector<T>> for Point<T> {
+    fn sub_assign(&mut self, vector: Vector<T>) {
+        self.x -= vector.x;
+        self.y -= vector.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 8 directions on a grid. Most puzzles only use the 4 [`Direction::ORTHOGONAL`] ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4 directions without diagonals, clockwise from north.
+    pub const ORTHOGONAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// All 8 directions, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Rotates clockwise by `eighths` of a full turn.
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % 8]
+    }
+
+    /// Rotates 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Rotates 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        (self as usize) % 2 == 1
+    }
+
+    /// The step in this direction, e.g. `(0, -1)` for north.
+    pub fn vector<T: From<i8>>(self) -> Vector<T> {
+        let (x, y) = match self {
+            Self::North => (0, -1),
+            Self::NorthEast => (1, -1),
+            Self::East => (1, 0),
+            Self::SouthEast => (1, 1),
+            Self::South => (0, 1),
+            Self::SouthWest => (-1, 1),
+            Self::West => (-1, 0),
+            Self::NorthWest => (-1, -1),
+        };
+        Vector::new(T::from(x), T::from(y))
+    }
+}
+
+/// Parses the arrows `^>v<`, the compass points `NESW` and the moves `UDLR`.
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Self::North),
+            '>' | 'E' | 'R' => Ok(Self::East),
+            'v' | 'S' | 'D' => Ok(Self::South),
+            '<' | 'W' | 'L' => Ok(Self::West),
+            _ => Err(DirectionFromCharError(c)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectionFromCharError(pub char);
+
+impl Error for DirectionFromCharError {}
+
+impl Display for DirectionFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a direction", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_with_points() {
+        let a = Point::new(3, -4);
+        let b = Point::new(-1, 2);
+
+        assert_eq!(a - b, Vector::new(4, -6));
+        assert_eq!(b + (a - b), a);
+        assert_eq!(a + Vector::new(1, 1) * 2, Point::new(5, -2));
+        assert_eq!(a - -Vector::new(1, 1), Point::new(4, -3));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::<u8>::new(1, 7).manhattan(Point::new(4, 2)), 8);
+        assert_eq!(a.to_string(), "3,-4");
+    }
+
+    #[test]
+    fn turns_directions() {
+        use Direction::*;
+
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(NorthEast.turn_right(), SouthEast);
+        assert_eq!(West.opposite(), East);
+        assert_eq!(SouthWest.opposite(), NorthEast);
+        assert!(NorthWest.is_diagonal() && !South.is_diagonal());
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.vector::<i64>(), -direction.opposite().vector());
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+        }
+    }
+
+    #[test]
+    fn parses_directions() {
+        let directions = |s: &str| s.chars().map(Direction::try_from).collect::<Vec<_>>();
+        let expected: Vec<_> = Direction::ORTHOGONAL.into_iter().map(Ok).collect();
+
+        assert_eq!(directions("^>v<"), expected);
+        assert_eq!(directions("NESW"), expected);
+        assert_eq!(directions("URDL"), expected);
+        assert_eq!(Direction::try_from('x'), Err(DirectionFromCharError('x')));
+        assert_eq!(Direction::East.vector::<i32>(), Vector::new(1, 0));
+    }
+}
@@ -0,0 +1,299 @@
+//! Connected components: a union-find structure, flood fill and the regions of a grid
+//! with their areas, perimeters and sides.
+use std::collections::VecDeque;
+
+use crate::{
+    grid::Grid,
+    point::{Direction, Point},
+};
+
+/// A disjoint-set forest over the elements `0..n`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates `n` sets that contain one element each.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Returns the representative of the set that contains `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets of `a` and `b`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (large, small) = if self.size[a] < self.size[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set of `element`.
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+
+    /// The number of disjoint sets.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns all points that can be reached from `start` by orthogonal steps between cells that
+/// `connects`, in the order of their distance to `start`.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Point,
+    mut connects: impl FnMut(&T, &T) -> bool,
+) -> Vec<Point> {
+    let mut visited = grid.map(|_| false);
+    let mut queue = VecDeque::new();
+    let mut reached = Vec::new();
+
+    if let Some(seen) = visited.get_mut(start) {
+        *seen = true;
+        queue.push_back(start);
+    }
+
+    while let Some(point) = queue.pop_front() {
+        reached.push(point);
+        for neighbor in grid.neighbors(point) {
+            if !visited[neighbor] && connects(&grid[point], &grid[neighbor]) {
+                visited[neighbor] = true;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    reached
+}
+
+/// The connected components of a grid, e.g. the garden plots of 2024 day 12.
+///
+/// Regions are numbered from 0, in the order of their first cell line by line.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    ids: Grid<usize>,
+    areas: Vec<usize>,
+    perimeters: Vec<usize>,
+    sides: Vec<usize>,
+}
+
+impl Regions {
+    /// Splits the grid into regions of orthogonally adjacent, equal cells.
+    pub fn of<T: PartialEq>(grid: &Grid<T>) -> Self {
+        Self::with(grid, |a, b| a == b)
+    }
+
+    /// Splits the grid into regions of orthogonally adjacent cells that `connects`.
+    /// `connects` has to be symmetric.
+    pub fn with<T>(grid: &Grid<T>, mut connects: impl FnMut(&T, &T) -> bool) -> Self {
+        let index = |p: Point| p.y as usize * grid.width() + p.x as usize;
+        let mut sets = UnionFind::new(grid.width() * grid.height());
+
+        for point in grid.points() {
+            for direction in [Direction::East, Direction::South] {
+                let neighbor = point + direction.vector();
+                if grid.contains(neighbor) && connects(&grid[point], &grid[neighbor]) {
+                    sets.union(index(point), index(neighbor));
+                }
+            }
+        }
+
+        // number the regions densely, in the order in which they are first seen.
+        let mut region_of_root = vec![usize::MAX; sets.len()];
+        let mut regions = 0;
+        let ids = Grid::from_fn(grid.width(), grid.height(), |point| {
+            let root = sets.find(index(point));
+            if region_of_root[root] == usize::MAX {
+                region_of_root[root] = regions;
+                regions += 1;
+            }
+            region_of_root[root]
+        });
+
+        let mut result = Self {
+            ids,
+            areas: vec![0; regions],
+            perimeters: vec![0; regions],
+            sides: vec![0; regions],
+        };
+        result.measure();
+        result
+    }
+
+    /// Counts the cells, borders and corners of every region. A region has as many sides as corners.
+    fn measure(&mut self) {
+        let ids = &self.ids;
+        let same = |point: Point, id: usize| ids.get(point) == Some(&id);
+
+        for (point, &id) in ids.iter() {
+            self.areas[id] += 1;
+
+            for direction in Direction::ORTHOGONAL {
+                let ahead = same(point + direction.vector(), id);
+                if !ahead {
+                    self.perimeters[id] += 1;
+                }
+
+                let turned = direction.turn_right();
+                let beside = same(point + turned.vector(), id);
+                let diagonal = same(point + direction.vector() + turned.vector(), id);
+                let outer_corner = !ahead && !beside;
+                let inner_corner = ahead && beside && !diagonal;
+                if outer_corner || inner_corner {
+                    self.sides[id] += 1;
+                }
+            }
+        }
+    }
+
+    /// The number of regions.
+    pub fn len(&self) -> usize {
+        self.areas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.areas.is_empty()
+    }
+
+    /// The region of every cell.
+    pub fn ids(&self) -> &Grid<usize> {
+        &self.ids
+    }
+
+    /// Returns the region of the cell at `point`.
+    pub fn id(&self, point: Point) -> Option<usize> {
+        self.ids.get(point).copied()
+    }
+
+    /// The number of cells in the region.
+    pub fn area(&self, id: usize) -> usize {
+        self.areas[id]
+    }
+
+    /// The number of cell borders between the region and other regions or the edge of the grid.
+    pub fn perimeter(&self, id: usize) -> usize {
+        self.perimeters[id]
+    }
+
+    /// The number of straight sections of the fence around the region, including the fences
+    /// around holes.
+    pub fn sides(&self, id: usize) -> usize {
+        self.sides[id]
+    }
+
+    /// Iterates over the points of a region, line by line.
+    pub fn points(&self, id: usize) -> impl Iterator<Item = Point> + '_ {
+        self.ids
+            .iter()
+            .filter(move |(_, &cell)| cell == id)
+            .map(|(point, _)| point)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unites_sets() {
+        let mut sets = UnionFind::new(6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.set_size(2), 4);
+        assert_eq!(sets.sets(), 3);
+    }
+
+    #[test]
+    fn fills_areas() {
+        let grid = Grid::parse("..#\n#..\n..#\n", |c| c);
+
+        let filled = flood_fill(&grid, Point::new(0, 0), |a, b| a == b);
+        assert_eq!(filled.len(), 6);
+        assert_eq!(filled[0], Point::new(0, 0));
+        assert!(!filled.contains(&Point::new(2, 0)));
+        assert!(flood_fill(&grid, Point::new(5, 5), |a, b| a == b).is_empty());
+    }
+
+    #[test]
+    fn measures_regions() {
+        let grid = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC\n", |c| c);
+        let regions = Regions::of(&grid);
+
+        assert_eq!(regions.len(), 5);
+        let measures: Vec<_> = (0..regions.len())
+            .map(|r| (regions.area(r), regions.perimeter(r), regions.sides(r)))
+            .collect();
+        assert_eq!(
+            measures,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(regions.id(Point::new(3, 3)), regions.id(Point::new(2, 1)));
+        assert_eq!(regions.points(3).collect::<Vec<_>>(), [Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn counts_sides_of_holes() {
+        let grid = Grid::parse("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n", |c| c);
+        let regions = Regions::of(&grid);
+
+        let a = regions.id(Point::new(0, 0)).unwrap();
+        assert_eq!(regions.area(a), 28);
+        assert_eq!(regions.sides(a), 12);
+        assert_eq!(regions.len(), 3);
+    }
+}
@@ -0,0 +1,204 @@
+/// Module that keeps track of answers that were accepted by the advent of code website.
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{encryption, Day};
+
+pub static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file, or its encrypted copy. If not present, returns no answers.
+    pub fn read_from_file() -> Self {
+        let s = match encryption::read_to_string(Path::new(ANSWERS_FILE_PATH)) {
+            Ok(s) => s,
+            Err(encryption::Error::IO(_)) => return Answers::default(),
+            Err(e) => {
+                eprintln!("{ANSWERS_FILE_PATH}: {e}");
+                return Answers::default();
+            }
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{ANSWERS_FILE_PATH}: {e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Record the accepted answer of a part, replacing a previously recorded answer.
+    pub fn record(&self, day: Day, part: u8, answer: &str) -> Self {
+        let mut answers = self.clone();
+
+        if !answers.data.iter().any(|a| a.day == day) {
+            answers.data.push(Answer {
+                day,
+                part_1: None,
+                part_2: None,
+            });
+            answers.data.sort_unstable_by_key(|a| a.day);
+        }
+
+        for entry in answers.data.iter_mut().filter(|a| a.day == day) {
+            match part {
+                1 => entry.part_1 = Some(answer.into()),
+                2 => entry.part_2 = Some(answer.into()),
+                _ => {}
+            }
+        }
+
+        answers
+    }
+
+    /// Get the recorded answers of a day.
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected answer.{key} to be null or string."))
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?.cloned(),
+            part_2: part("part_2")?.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "11", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        let answer = answers.get(day!(1)).unwrap();
+        assert_eq!(answer.part_1, Some("11".to_string()));
+        assert_eq!(answer.part_2, None);
+    }
+
+    #[test]
+    fn records_answers() {
+        let answers = Answers::default()
+            .record(day!(5), 1, "143")
+            .record(day!(2), 2, "4")
+            .record(day!(5), 2, "123")
+            .record(day!(5), 2, "76");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(2));
+        assert_eq!(answers.data[0].part_1, None);
+        assert_eq!(answers.data[1].part_1, Some("143".to_string()));
+        assert_eq!(answers.data[1].part_2, Some("76".to_string()));
+    }
+}
@@ -49,8 +49,8 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
+/// Download the input to `input_path` and the puzzle description to its default location.
+pub fn download(day: Day, input_path: &str) -> Result<Output, AocCommandError> {
     let puzzle_path = get_puzzle_path(day);
 
     let args = build_args(
@@ -60,16 +60,30 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--input-file".into(),
             input_path.to_string(),
             "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            puzzle_path,
         ],
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    call_aoc_cli(&args)
+}
+
+/// Download only the puzzle description, e.g. to pick up part two after solving part one.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path,
+        ],
+        day,
+    );
+
+    call_aoc_cli(&args)
 }
 
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
@@ -77,18 +91,30 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = call_aoc_cli_captured(&args)?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(output)
+}
+
+/// Checks whether the output of a `submit` call reports the answer as correct.
+pub fn is_correct_answer(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
-fn get_input_path(day: Day) -> String {
+pub(crate) fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub(crate) fn get_example_path(day: Day) -> String {
+    format!("data/examples/{day}.txt")
+}
+
+pub(crate) fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -109,10 +135,19 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with_stdout(args, Stdio::inherit())
+}
+
+/// Like `call_aoc_cli`, but keeps the output so that it can be inspected.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with_stdout(args, Stdio::piped())
+}
+
+fn call_aoc_cli_with_stdout(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
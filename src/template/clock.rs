@@ -0,0 +1,70 @@
+use chrono::Utc;
+
+/// A source of the current time, so that time dependent features can be tested.
+pub trait Clock {
+    /// Returns the current unix timestamp in seconds.
+    fn now(&self) -> i64;
+}
+
+/// The clock of the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        Utc::now().timestamp()
+    }
+}
+
+/// A clock that is stopped at the given unix timestamp.
+///
+/// ```
+/// # use advent_of_code::template::{Clock, FixedClock};
+/// assert_eq!(FixedClock(1_733_029_200).now(), 1_733_029_200);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub i64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.0
+    }
+}
+
+/// Formats a number of seconds between two events, e.g. an unlock and a star, as `[Nd ]hh:mm:ss`.
+/// A missing duration is formatted as `-`.
+pub(crate) fn format_secs(secs: Option<i64>) -> String {
+    let Some(secs) = secs else {
+        return "-".into();
+    };
+
+    let sign = if secs < 0 { "-" } else { "" };
+    let secs = secs.abs();
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    match secs / 86400 {
+        0 => format!("{sign}{time}"),
+        days => format!("{sign}{days}d {time}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_secs;
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_secs(None), "-");
+        assert_eq!(format_secs(Some(100)), "00:01:40");
+        assert_eq!(format_secs(Some(-100)), "-00:01:40");
+        assert_eq!(format_secs(Some(3 * 3600 + 5)), "03:00:05");
+        assert_eq!(format_secs(Some(2 * 86400 + 61)), "2d 00:01:01");
+    }
+}
@@ -1,5 +1,12 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::run_multi;
+use crate::template::timings::Timings;
+use crate::template::{all_days, DaySelection};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: Option<DaySelection>, is_release: bool, jobs: usize) {
+    let days_to_run = days.map_or_else(
+        || all_days().collect(),
+        |days| days.resolve(&Timings::read_from_file()),
+    );
+
+    run_multi(&days_to_run, is_release, false, jobs);
 }
@@ -1,14 +1,118 @@
+use crate::template::aoc_cli::{get_input_path, get_puzzle_path};
+use crate::template::input_cache::{cache_key, is_logged_out_response, InputCache};
 use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fmt::Display, fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// A failed download, with a message describing the failure.
+#[derive(Debug)]
+pub enum Error {
+    /// Calling aoc-cli failed, e.g. because the server did not serve the puzzle yet.
+    Transient(String),
+    /// Retrying does not help, e.g. aoc-cli is missing or the session cookie expired.
+    Fatal(String),
+}
+
+impl Error {
+    /// Whether the download may succeed when it is retried.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Error::Transient(_))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Transient(message) | Error::Fatal(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+fn check_aoc_cli() -> Result<(), Error> {
+    aoc_cli::check().map_err(|_| {
+        Error::Fatal("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into())
+    })
+}
+
+fn write_input(day: Day, input: &str) -> Result<(), Error> {
+    let input_path = get_input_path(day);
+    fs::write(&input_path, input)
+        .map_err(|e| Error::Fatal(format!("Failed to write input file \"{input_path}\": {e}")))
+}
+
+/// Downloads the input and puzzle of `day`, returning an error describing the failure otherwise.
+pub fn try_handle(day: Day, force: bool, offline: bool) -> Result<(), Error> {
+    let mut cache = InputCache::read_from_file();
+    let key = cache_key(day);
+
+    if !force {
+        if let Some(input) = cache.get(&key) {
+            write_input(day, &input)?;
+            println!("🎄 Using cached input for \"{key}\".");
+
+            if !offline {
+                check_aoc_cli()?;
+                aoc_cli::download_puzzle(day)
+                    .map_err(|e| Error::Transient(format!("failed to call aoc-cli: {e}")))?;
+                println!(
+                    "🎄 Successfully wrote puzzle to \"{}\".",
+                    get_puzzle_path(day)
+                );
+            }
+
+            return Ok(());
+        }
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if offline {
+        return Err(Error::Fatal(format!(
+            "Input for \"{key}\" is not cached, it can not be downloaded in offline mode."
+        )));
+    }
+
+    check_aoc_cli()?;
+
+    // download to a separate file first, so that a bad response does not replace a good input.
+    let download_path = InputCache::download_path(&key);
+    if let Some((dir, _)) = download_path.rsplit_once('/') {
+        let _ = fs::create_dir_all(dir);
+    }
+
+    aoc_cli::download(day, &download_path)
+        .map_err(|e| Error::Transient(format!("failed to call aoc-cli: {e}")))?;
+
+    let input = fs::read_to_string(&download_path).unwrap_or_default();
+    let _ = fs::remove_file(&download_path);
+
+    if input.is_empty() || is_logged_out_response(&input) {
+        return Err(Error::Fatal(
+            "The downloaded input is empty or asks to log in, your session cookie probably expired. \
+            Kept the existing input file."
+                .into(),
+        ));
+    }
+
+    if let Err(e) = cache.insert(&key, &input).and_then(|()| cache.store_file()) {
+        eprintln!("Failed to cache input: {e}");
+    }
+
+    write_input(day, &input)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        get_input_path(day)
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        get_puzzle_path(day)
+    );
+
+    Ok(())
+}
+
+pub fn handle(day: Day, force: bool, offline: bool) {
+    if let Err(e) = try_handle(day, force, offline) {
+        eprintln!("{e}");
         process::exit(1);
-    };
+    }
 }
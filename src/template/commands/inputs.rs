@@ -0,0 +1,207 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process,
+    str::FromStr,
+};
+
+use crate::template::answers::ANSWERS_FILE_PATH;
+use crate::template::encryption::{self, Key};
+
+static INPUTS_DIR: &str = "./data/inputs";
+
+/// Subcommand of `cargo inputs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Encrypt,
+    Decrypt,
+    Keygen,
+}
+
+/// Plain text files that are encrypted: the inputs and the recorded answers.
+fn plain_files() -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(INPUTS_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+
+    files.push(ANSWERS_FILE_PATH.into());
+    files.sort_unstable();
+    files
+}
+
+fn read_key() -> Key {
+    match Key::from_env() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to read key: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn encrypt() {
+    let key = read_key();
+    let mut count = 0;
+
+    for path in plain_files() {
+        let Ok(plain) = fs::read(&path) else {
+            continue;
+        };
+
+        if plain.is_empty() {
+            continue;
+        }
+
+        let encrypted_path = encryption::encrypted_path(&path);
+
+        // every encryption uses a fresh nonce, keep unchanged files as they are to avoid noise in diffs.
+        let unchanged = fs::read(&encrypted_path)
+            .ok()
+            .and_then(|sealed| encryption::open(&key, &sealed).ok())
+            .is_some_and(|existing| existing == plain);
+
+        if unchanged {
+            continue;
+        }
+
+        if let Err(e) = fs::write(&encrypted_path, encryption::seal(&key, &plain)) {
+            eprintln!("Failed to write \"{encrypted_path}\": {e}");
+            process::exit(1);
+        }
+
+        println!("Encrypted \"{path}\" to \"{encrypted_path}\"");
+        count += 1;
+    }
+
+    println!("---");
+    println!("🎄 Encrypted {count} changed file(s).");
+}
+
+fn decrypt() {
+    let key = read_key();
+    let mut count = 0;
+
+    for path in plain_files()
+        .into_iter()
+        .chain(encrypted_inputs_without_plain())
+    {
+        let encrypted_path = encryption::encrypted_path(&path);
+        let Ok(sealed) = fs::read(&encrypted_path) else {
+            continue;
+        };
+
+        let plain = match encryption::open(&key, &sealed) {
+            Ok(plain) => plain,
+            Err(e) => {
+                eprintln!("Failed to decrypt \"{encrypted_path}\": {e}");
+                process::exit(1);
+            }
+        };
+
+        if fs::read(&path).is_ok_and(|existing| existing == plain) {
+            continue;
+        }
+
+        if let Err(e) = fs::write(&path, plain) {
+            eprintln!("Failed to write \"{path}\": {e}");
+            process::exit(1);
+        }
+
+        println!("Decrypted \"{encrypted_path}\" to \"{path}\"");
+        count += 1;
+    }
+
+    println!("---");
+    println!("🎄 Decrypted {count} file(s).");
+}
+
+/// Plain text paths of encrypted inputs, for which no plain text file exists yet.
+fn encrypted_inputs_without_plain() -> Vec<String> {
+    let suffix = format!(".txt.{}", encryption::ENCRYPTED_EXTENSION);
+
+    fs::read_dir(INPUTS_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path().to_string_lossy().to_string();
+            let plain = path.strip_suffix(&suffix)?.to_string() + ".txt";
+            (!Path::new(&plain).exists()).then_some(plain)
+        })
+        .collect()
+}
+
+fn keygen() {
+    let path = encryption::key_file_path();
+
+    // create the file atomically, readable by the owner only.
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let written = options
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", Key::generate().to_hex()));
+
+    match written {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!("Key file \"{path}\" already exists.");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to write key file \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+
+    println!("🎄 Wrote a new key to \"{path}\". Share it privately, never commit it.");
+}
+
+pub fn handle(action: Action) {
+    match action {
+        Action::Encrypt => encrypt(),
+        Action::Decrypt => decrypt(),
+        Action::Keygen => keygen(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Action {
+    type Err = ActionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            "keygen" => Ok(Action::Keygen),
+            s => Err(ActionFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Action`].
+#[derive(Debug)]
+pub struct ActionFromStrError(String);
+
+impl Error for ActionFromStrError {}
+
+impl Display for ActionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid action `{}`, expecting one of `encrypt`, `decrypt`, `keygen`",
+            self.0
+        )
+    }
+}
@@ -0,0 +1,45 @@
+use std::{fs, process};
+
+use crate::template::aoc_cli::get_year;
+use crate::template::leaderboard::{self, Leaderboard, Scoring};
+use crate::template::{all_days, Day};
+
+pub fn handle(path: &str, scoring: Scoring, day: Option<Day>, fetch: Option<String>) {
+    if let Some(id) = fetch {
+        let Some(year) = get_year() else {
+            eprintln!(
+                "Fetching a leaderboard requires the AOC_YEAR environment variable to be set."
+            );
+            process::exit(1);
+        };
+
+        if let Err(e) = leaderboard::fetch(&id, year, path) {
+            eprintln!("Failed to fetch leaderboard: {e}");
+            process::exit(1);
+        }
+
+        println!("🎄 Successfully wrote leaderboard to \"{path}\".\n");
+    }
+
+    let Ok(json) = fs::read_to_string(path) else {
+        eprintln!("Could not read leaderboard file \"{path}\".");
+        process::exit(1);
+    };
+
+    let leaderboard = match Leaderboard::try_from(json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{}", leaderboard::render_standings(&leaderboard, scoring));
+
+    for day in all_days().filter(|d| day.is_none_or(|day| day == *d)) {
+        let table = leaderboard::render_day(&leaderboard, day);
+        if !table.is_empty() {
+            println!("{table}");
+        }
+    }
+}
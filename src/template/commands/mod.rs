@@ -1,6 +1,14 @@
 pub mod all;
 pub mod download;
+pub mod inputs;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod stats;
+pub mod status;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
+pub mod verify;
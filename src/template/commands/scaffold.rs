@@ -1,79 +1,199 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
-
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
-
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+use std::{fs, path::Path, process};
+
+use crate::template::module_template::{self, Placeholders};
+use crate::template::stats::{self, Event};
+use crate::template::{encryption, Day};
+
+/// What scaffolding does to a single file.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    /// The file does not exist yet.
+    Create,
+    /// The file exists and is left untouched.
+    Skip,
+    /// The file does not exist, but its encrypted copy does. An empty placeholder would hide it.
+    SkipEncrypted,
+    /// The file exists and is replaced. Non-empty files are backed up to the contained path first.
+    Overwrite(Option<String>),
+}
+
+struct PlannedFile {
+    description: &'static str,
+    path: String,
+    contents: String,
+    action: Action,
+}
+
+impl PlannedFile {
+    fn new(description: &'static str, path: String, contents: String, overwrite: bool) -> Self {
+        let action = plan(&path, overwrite);
+        PlannedFile {
+            description,
+            path,
+            contents,
+            action,
+        }
+    }
+
+    fn execute(&self) -> Result<(), std::io::Error> {
+        match &self.action {
+            Action::Skip | Action::SkipEncrypted => Ok(()),
+            Action::Create | Action::Overwrite(None) => fs::write(&self.path, &self.contents),
+            Action::Overwrite(Some(backup_path)) => {
+                fs::copy(&self.path, backup_path)?;
+                fs::write(&self.path, &self.contents)
+            }
+        }
+    }
+
+    fn report(&self, dry_run: bool) -> String {
+        let path = &self.path;
+        let description = match self.action {
+            Action::Create if self.contents.is_empty() => format!("empty {}", self.description),
+            _ => self.description.to_string(),
+        };
+
+        let (verb, details) = match &self.action {
+            Action::Create => (["Created", "Would create"], String::new()),
+            Action::Skip => (["Skipped", "Would skip"], " (already exists)".into()),
+            Action::SkipEncrypted => (
+                ["Skipped", "Would skip"],
+                " (encrypted copy exists, see `cargo inputs decrypt`)".into(),
+            ),
+            Action::Overwrite(None) => (["Overwrote", "Would overwrite"], String::new()),
+            Action::Overwrite(Some(backup_path)) => (
+                ["Overwrote", "Would overwrite"],
+                format!(" (backup in \"{backup_path}\")"),
+            ),
+        };
+
+        format!(
+            "{} {description} \"{path}\"{details}",
+            verb[usize::from(dry_run)]
+        )
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Decide what to do with the file at `path`.
+fn plan(path: &str, overwrite: bool) -> Action {
+    match fs::metadata(path) {
+        Err(_) if Path::new(&encryption::encrypted_path(path)).exists() => Action::SkipEncrypted,
+        Err(_) => Action::Create,
+        Ok(_) if !overwrite => Action::Skip,
+        Ok(metadata) if metadata.len() == 0 => Action::Overwrite(None),
+        Ok(_) => Action::Overwrite(Some(backup_path(path))),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// The first of `<path>.bak`, `<path>.bak.1`, ... that does not exist yet.
+fn backup_path(path: &str) -> String {
+    (0..)
+        .map(|i| match i {
+            0 => format!("{path}.bak"),
+            i => format!("{path}.bak.{i}"),
+        })
+        .find(|candidate| !Path::new(candidate).exists())
+        .unwrap()
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+pub fn handle(day: Day, overwrite: bool, dry_run: bool, template: Option<&str>) {
+    let template = template.unwrap_or(module_template::DEFAULT_TEMPLATE);
+    let module_template = match module_template::load(template) {
+        Ok(module_template) => module_template,
+        Err(module_template::Error::NotFound(available)) => {
+            eprintln!(
+                "Template \"{template}\" not found, available templates: {}.",
+                available.join(", ")
+            );
+            process::exit(1);
+        }
+        Err(module_template::Error::IO(e)) => {
+            eprintln!("Failed to read template \"{template}\": {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    // inputs and examples are never overwritten, they may contain downloaded or hand-written data.
+    let files = [
+        PlannedFile::new(
+            "module file",
+            format!("src/bin/{day}.rs"),
+            Placeholders::collect(day).render(&module_template),
+            overwrite,
+        ),
+        PlannedFile::new(
+            "input file",
+            format!("data/inputs/{day}.txt"),
+            String::new(),
+            false,
+        ),
+        PlannedFile::new(
+            "example file",
+            format!("data/examples/{day}.txt"),
+            String::new(),
+            false,
+        ),
+    ];
+
+    if dry_run {
+        for file in &files {
+            println!("{}", file.report(true));
         }
+        println!("---");
+        println!("🎄 Dry run, no files were changed.");
+        return;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+    for file in &files {
+        if let Err(e) = file.execute() {
+            eprintln!(
+                "Failed to write {} \"{}\": {e}",
+                file.description, file.path
+            );
             process::exit(1);
         }
+        println!("{}", file.report(false));
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if files[0].action != Action::Skip {
+        stats::record_now(day, Event::Scaffolded);
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{plan, Action};
+    use std::{env, fs, process};
+
+    #[test]
+    fn plans_file_actions() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = dir.join("missing.txt").to_string_lossy().to_string();
+        let encrypted = dir.join("encrypted.txt").to_string_lossy().to_string();
+        let empty = dir.join("empty.txt").to_string_lossy().to_string();
+        let filled = dir.join("filled.txt").to_string_lossy().to_string();
+        fs::write(&empty, "").unwrap();
+        fs::write(&filled, "input").unwrap();
+        fs::write(format!("{filled}.bak"), "older input").unwrap();
+        fs::write(format!("{encrypted}.enc"), "sealed input").unwrap();
+
+        assert_eq!(plan(&missing, false), Action::Create);
+        assert_eq!(plan(&encrypted, false), Action::SkipEncrypted);
+        assert_eq!(plan(&empty, false), Action::Skip);
+        assert_eq!(plan(&filled, false), Action::Skip);
+        assert_eq!(plan(&empty, true), Action::Overwrite(None));
+        assert_eq!(
+            plan(&filled, true),
+            Action::Overwrite(Some(format!("{filled}.bak.1")))
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
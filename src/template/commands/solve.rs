@@ -1,8 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands;
+use crate::template::stats::{self, Event};
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, Day};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    store: bool,
+    verbose: bool,
+    submit_part: Option<u8>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,11 +28,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    stats::record_now(day, Event::FirstRun);
+
+    if verbose {
+        cmd_args.push("--verbose".to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if dhat && store {
+        let output = match child_commands::run_and_capture(Command::new("cargo").args(&cmd_args)) {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("Failed to run solution: {e:?}");
+                process::exit(1);
+            }
+        };
+
+        store_allocations(day, &output);
+        return;
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +61,25 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+fn store_allocations(day: Day, output: &[String]) {
+    let (part_1, part_2) = child_commands::parse_allocations(output);
+
+    if part_1.is_none() && part_2.is_none() {
+        eprintln!("No allocation profile found in solution output.");
+        return;
+    }
+
+    let timings = Timings::read_from_file().with_allocations(day, part_1, part_2);
+    timings.store_file().unwrap();
+
+    println!();
+    match readme_benchmarks::update(timings) {
+        Ok(()) => {
+            println!("Stored updated allocations.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated allocations.");
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::{env, fs, process};
+
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks;
+use crate::template::readme_stars::{self, Stars};
+
+pub fn handle(leaderboard: Option<String>, user: Option<String>) {
+    let stars = match leaderboard {
+        Some(path) => {
+            let Ok(json) = fs::read_to_string(&path) else {
+                eprintln!("Could not read leaderboard file \"{path}\".");
+                process::exit(1);
+            };
+
+            let user = user.or_else(|| env::var("AOC_USER_ID").ok());
+            match Stars::from_leaderboard(&json, user.as_deref()) {
+                Ok(stars) => stars,
+                Err(readme_benchmarks::Error::Parser(e)) => {
+                    eprintln!("Failed to read leaderboard: {e}");
+                    process::exit(1);
+                }
+                Err(readme_benchmarks::Error::IO(e)) => {
+                    eprintln!("Failed to read leaderboard: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+        None => Stars::from_answers(&Answers::read_from_file()),
+    };
+
+    match readme_stars::update(&stars) {
+        Ok(()) => println!("Updated stars table ({} ⭐).", stars.count()),
+        Err(_) => {
+            eprintln!("Failed to update the stars table.");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::template::stats::{self, Stats};
+
+pub fn handle() {
+    let stats = Stats::read_from_file();
+
+    if stats.data.is_empty() {
+        println!("No solve stats recorded yet. They are collected by `scaffold`, `solve` and `solve --submit`.");
+        return;
+    }
+
+    println!("{}", stats::render(&stats));
+}
@@ -0,0 +1,5 @@
+use crate::template::status;
+
+pub fn handle(markdown: bool) {
+    status::print(markdown);
+}
@@ -1,13 +1,11 @@
-use std::collections::HashSet;
-
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, DaySelection};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(days: Option<DaySelection>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
@@ -18,10 +16,10 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.resolve(&stored_timings),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, 1).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -0,0 +1,98 @@
+use std::io::{self, Write};
+use std::time::Duration;
+use std::{fs, process, thread};
+
+use crate::template::aoc_cli::{get_example_path, get_puzzle_path};
+use crate::template::commands::{download, read, scaffold};
+use crate::template::module_template::find_example_input;
+use crate::template::{format_secs, next_unlock_with, Clock, Day, SystemClock};
+
+/// Number of download attempts after the puzzle unlocked, the server may not serve it right away.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+
+/// Prints a countdown on a single line until `unlock_time` is reached.
+fn wait_for_unlock(clock: &impl Clock, day: Day, unlock_time: i64) {
+    loop {
+        let remaining = unlock_time - clock.now();
+        if remaining <= 0 {
+            break;
+        }
+
+        print!(
+            "\r⏳ Day {day} unlocks in {}  ",
+            format_secs(Some(remaining))
+        );
+        let _ = io::stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    println!("\r🔔 Day {day} is unlocked!                  ");
+}
+
+/// Downloads the input, retrying transient failures with an exponential backoff.
+fn download_with_retry(day: Day) {
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match download::try_handle(day, false, false) {
+            Ok(()) => return,
+            Err(e) if e.is_transient() && attempt < DOWNLOAD_ATTEMPTS => {
+                let backoff = 2_u64.pow(attempt - 1);
+                eprintln!("{e}");
+                eprintln!("Retrying in {backoff}s ({attempt}/{DOWNLOAD_ATTEMPTS})...");
+                thread::sleep(Duration::from_secs(backoff));
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Writes the first example of the puzzle to the example file, unless it already has contents.
+fn extract_example(day: Day) {
+    let example_path = get_example_path(day);
+    if fs::read_to_string(&example_path).is_ok_and(|example| !example.is_empty()) {
+        return;
+    }
+
+    let puzzle = fs::read_to_string(get_puzzle_path(day)).unwrap_or_default();
+    match find_example_input(&puzzle) {
+        Some(example) => match fs::write(&example_path, example) {
+            Ok(()) => println!("🎄 Extracted example to \"{example_path}\"."),
+            Err(e) => eprintln!("Failed to write example file \"{example_path}\": {e}"),
+        },
+        None => eprintln!("Could not find an example in the puzzle, please copy it manually."),
+    }
+}
+
+pub fn handle(wait: bool) {
+    let clock = SystemClock;
+
+    let day = match (Day::today_with(&clock), wait) {
+        // during advent, wait for the puzzle of tomorrow.
+        (_, true) => {
+            let Some((day, unlock_time)) = next_unlock_with(&clock) else {
+                eprintln!("Could not determine the next puzzle unlock.");
+                process::exit(1);
+            };
+
+            wait_for_unlock(&clock, day, unlock_time);
+            day
+        }
+        (Some(day), false) => day,
+        (None, false) => {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day \
+                or `today --wait` to wait for the next puzzle."
+            );
+            process::exit(1)
+        }
+    };
+
+    // download first, so that the template can be filled in with the title and example answers.
+    download_with_retry(day);
+    extract_example(day);
+    scaffold::handle(day, false, false, None);
+    read::handle(day);
+}
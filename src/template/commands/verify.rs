@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{child_commands::parse_answers, run_days};
+use crate::template::timings::Timings;
+use crate::template::{Day, DaySelection, ANSI_BOLD, ANSI_RESET};
+
+/// Compares a result with the recorded answer, returning a description of a mismatch.
+fn check(recorded: Option<&String>, result: Option<&String>) -> Option<String> {
+    match (recorded, result) {
+        (Some(recorded), Some(result)) if recorded == result => None,
+        (Some(recorded), Some(result)) => Some(format!("expected {recorded}, got {result}")),
+        (Some(recorded), None) => Some(format!("expected {recorded}, got no result")),
+        (None, _) => None,
+    }
+}
+
+pub fn handle(days: Option<DaySelection>, is_release: bool, jobs: usize) {
+    let answers = Answers::read_from_file();
+
+    // without a selection, verify every day that has a recorded answer.
+    let days_to_run: HashSet<Day> = days.map_or_else(
+        || answers.data.iter().map(|a| a.day).collect(),
+        |days| days.resolve(&Timings::read_from_file()),
+    );
+
+    let outputs = run_days(&days_to_run, is_release, false, jobs);
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for (day, output) in outputs {
+        let Some(answer) = answers.get(day) else {
+            println!("Day {day}: no recorded answers.");
+            continue;
+        };
+
+        let (part_1, part_2) = parse_answers(&output);
+        let parts = [
+            (1, answer.part_1.as_ref(), part_1.as_ref()),
+            (2, answer.part_2.as_ref(), part_2.as_ref()),
+        ];
+
+        for (part, recorded, result) in parts {
+            if recorded.is_none() {
+                continue;
+            }
+
+            checked += 1;
+            match check(recorded, result) {
+                None => println!("Day {day} part {part}: ✔"),
+                Some(mismatch) => {
+                    failed += 1;
+                    println!("Day {day} part {part}: ✖ {mismatch}");
+                }
+            }
+        }
+    }
+
+    println!("---");
+    if failed > 0 {
+        println!("🎄 {failed} of {checked} recorded answer(s) do not match.");
+        process::exit(1);
+    }
+
+    println!("🎄 All {checked} recorded answer(s) match.");
+}
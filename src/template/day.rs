@@ -2,10 +2,11 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate};
 
-#[cfg(feature = "today")]
+use crate::template::Clock;
+
+/// Offset of the timezone the puzzles unlock in (EST), in hours.
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
@@ -41,19 +42,60 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the unix timestamp at which the puzzle of this day unlocks in `year`,
+    /// i.e. midnight of the december day in the timezone of the server.
+    pub fn unlock_time(self, year: u16) -> i64 {
+        NaiveDate::from_ymd_opt(i32::from(year), 12, u32::from(self.0))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .and_then(|midnight| midnight.and_local_timezone(server_timezone()).single())
+            .expect("every day of advent is a valid date")
+            .timestamp()
+    }
+
+    /// Returns the day of advent at the time of `clock` in the timezone of the server,
+    /// `None` outside of the 1st to the 25th of december.
+    pub fn today_with(clock: &impl Clock) -> Option<Self> {
+        let date = server_date(clock.now())?;
+        if date.month() == 12 {
+            Self::new(u8::try_from(date.day()).ok()?)
+        } else {
+            None
+        }
+    }
+}
+
+fn server_timezone() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).expect("the server offset is in range")
+}
+
+/// Returns the date of the unix timestamp `now` in the timezone of the server.
+fn server_date(now: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(now, 0).map(|time| time.with_timezone(&server_timezone()).date_naive())
+}
+
+/// Returns the first day of advent in `year` or the year after that unlocks after the unix
+/// timestamp `now`, together with its unlock time.
+pub fn next_unlock(now: i64, year: u16) -> Option<(Day, i64)> {
+    [year, year + 1]
+        .into_iter()
+        .flat_map(|year| all_days().map(move |day| (day, day.unlock_time(year))))
+        .find(|(_, unlock_time)| *unlock_time > now)
+}
+
+/// Returns the next puzzle to unlock after the time of `clock`, together with its unlock time.
+/// During advent, this is the puzzle of tomorrow.
+pub fn next_unlock_with(clock: &impl Clock) -> Option<(Day, i64)> {
+    let now = clock.now();
+    let year = server_date(now)?.year();
+    next_unlock(now, u16::try_from(year).ok()?)
 }
 
 #[cfg(feature = "today")]
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+        Self::today_with(&crate::template::SystemClock)
     }
 }
 
@@ -154,7 +196,8 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, next_unlock, next_unlock_with, Day};
+    use crate::template::FixedClock;
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +230,67 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn unlock_time() {
+        // 2024-12-01T05:00:00Z and 2023-12-25T05:00:00Z
+        assert_eq!(Day(1).unlock_time(2024), 1_733_029_200);
+        assert_eq!(Day(25).unlock_time(2023), 1_703_480_400);
+    }
+
+    #[test]
+    fn today_in_server_timezone() {
+        let today = |timestamp| Day::today_with(&FixedClock(timestamp));
+
+        // 2024-11-30T12:00:00Z
+        assert_eq!(today(1_732_968_000), None);
+        // 2024-12-01T04:59:59Z, still the 30th of november in EST.
+        assert_eq!(today(1_733_029_199), None);
+        // 2024-12-01T05:00:00Z
+        assert_eq!(today(1_733_029_200), Some(Day(1)));
+        // 2024-12-25T12:00:00Z
+        assert_eq!(today(1_735_128_000), Some(Day(25)));
+        // 2024-12-26T04:59:59Z, still the 25th in EST.
+        assert_eq!(today(1_735_189_199), Some(Day(25)));
+        // 2024-12-26T05:00:00Z
+        assert_eq!(today(1_735_189_200), None);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let day_1 = Day(1).unlock_time(2024);
+        let day_25 = Day(25).unlock_time(2024);
+
+        assert_eq!(next_unlock(day_1 - 10, 2024), Some((Day(1), day_1)));
+        assert_eq!(next_unlock(day_1, 2024), Some((Day(2), day_1 + 86400)));
+        assert_eq!(
+            next_unlock(day_25, 2024),
+            Some((Day(1), Day(1).unlock_time(2025)))
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock_during_advent() {
+        let next = |timestamp| next_unlock_with(&FixedClock(timestamp));
+
+        // 2024-12-10T20:00:00Z, the puzzle of the 10th is already unlocked.
+        assert_eq!(
+            next(1_733_860_800),
+            Some((Day(11), Day(11).unlock_time(2024)))
+        );
+        // 2024-12-11T04:59:59Z, still the 10th in EST.
+        assert_eq!(next(1_733_893_199), Some((Day(11), 1_733_893_200)));
+        // 2024-11-30T12:00:00Z
+        assert_eq!(
+            next(1_732_968_000),
+            Some((Day(1), Day(1).unlock_time(2024)))
+        );
+        // 2024-12-31T12:00:00Z
+        assert_eq!(
+            next(1_735_646_400),
+            Some((Day(1), Day(1).unlock_time(2025)))
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
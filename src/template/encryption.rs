@@ -0,0 +1,243 @@
+/// Module that encrypts inputs and answers at rest, so that they can be committed.
+///
+/// Files are sealed with ChaCha20-Poly1305 (RFC 8439) from the `chacha20poly1305` crate.
+/// Keys and nonces are drawn from the random number generator of the operating system.
+use std::{env, fmt, fs, io, path::Path};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+
+/// Extension of encrypted files, appended to the name of the plain text file.
+pub const ENCRYPTED_EXTENSION: &str = "enc";
+
+/// Default location of the key file.
+pub const DEFAULT_KEY_FILE: &str = "./.aoc-key";
+
+/// Header of encrypted files, also authenticated as associated data.
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+#[derive(Debug)]
+pub enum Error {
+    /// No key is configured.
+    MissingKey,
+    /// The configured key is not 64 hex characters.
+    InvalidKey,
+    /// The file is not encrypted, was encrypted with another key or was modified.
+    Decrypt,
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no key found, set AOC_INPUT_KEY or create a key file with `cargo inputs keygen`."
+            ),
+            Error::InvalidKey => write!(f, "the key has to consist of 64 hex characters."),
+            Error::Decrypt => write!(f, "decryption failed, is the key correct?"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A 256-bit key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Read the key from `AOC_INPUT_KEY`, or else from the file at `AOC_INPUT_KEY_FILE`
+    /// (default: `.aoc-key`).
+    pub fn from_env() -> Result<Self, Error> {
+        let hex = match env::var("AOC_INPUT_KEY") {
+            Ok(hex) => hex,
+            Err(_) => fs::read_to_string(key_file_path()).map_err(|_| Error::MissingKey)?,
+        };
+
+        Key::from_hex(hex.trim())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(Error::InvalidKey);
+        }
+
+        let mut key = [0; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| Error::InvalidKey)?;
+        }
+
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Generate a random key.
+    pub fn generate() -> Self {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+pub fn key_file_path() -> String {
+    env::var("AOC_INPUT_KEY_FILE").unwrap_or_else(|_| DEFAULT_KEY_FILE.into())
+}
+
+/// Path of the encrypted counterpart of `path`.
+pub fn encrypted_path(path: &str) -> String {
+    format!("{path}.{ENCRYPTED_EXTENSION}")
+}
+
+/// Read a text file, decrypting its encrypted counterpart if the file is missing or empty.
+/// Empty files are placeholders, e.g. created by `cargo scaffold`, and do not hide the encrypted copy.
+pub fn read_to_string(path: &Path) -> Result<String, Error> {
+    read_to_string_with(path, Key::from_env)
+}
+
+/// [`read_to_string`] with a custom source of the key, which is only read for encrypted files.
+fn read_to_string_with(
+    path: &Path,
+    key: impl FnOnce() -> Result<Key, Error>,
+) -> Result<String, Error> {
+    let plain = match fs::read_to_string(path) {
+        Ok(plain) if !plain.is_empty() => return Ok(plain),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        plain => plain,
+    };
+
+    let Ok(sealed) = fs::read(encrypted_path(&path.to_string_lossy())) else {
+        return Ok(plain?);
+    };
+    let plain = open(&key()?, &sealed)?;
+    String::from_utf8(plain).map_err(|_| Error::Decrypt)
+}
+
+/// Encrypt and authenticate `plain`. The output contains a header and a random nonce.
+pub fn seal(key: &Key, plain: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: plain,
+        aad: MAGIC,
+    };
+
+    // encryption only fails for messages of more than 256 GiB.
+    let ciphertext = key
+        .cipher()
+        .encrypt(&nonce, payload)
+        .expect("input is too large to be encrypted");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// Verify and decrypt the output of [`seal`].
+pub fn open(key: &Key, sealed: &[u8]) -> Result<Vec<u8>, Error> {
+    let body = sealed.strip_prefix(MAGIC).ok_or(Error::Decrypt)?;
+    if body.len() < NONCE_LEN + TAG_LEN {
+        return Err(Error::Decrypt);
+    }
+
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: MAGIC,
+    };
+
+    key.cipher()
+        .decrypt(Nonce::from_slice(nonce), payload)
+        .map_err(|_| Error::Decrypt)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use super::{encrypted_path, open, read_to_string_with, seal, Error, Key};
+
+    #[test]
+    fn seals_and_opens() {
+        let key = Key::generate();
+        let plain = b"3   4\n4   3\n2   5\n";
+        let sealed = seal(&key, plain);
+
+        assert_eq!(open(&key, &sealed).unwrap(), plain.to_vec());
+        assert_eq!(open(&key, &seal(&key, b"")).unwrap(), b"".to_vec());
+        assert!(open(&Key::generate(), &sealed).is_err());
+        assert_ne!(seal(&key, plain), sealed);
+
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap() == key, true);
+        assert!(Key::from_hex("abc").is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_files() {
+        let key = Key::generate();
+        let sealed = seal(&key, b"3   4\n");
+
+        // every part of the file is authenticated: the header, the nonce, the ciphertext and the tag.
+        for index in [0, 8, 20, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[index] ^= 1;
+            assert!(matches!(open(&key, &tampered), Err(Error::Decrypt)));
+        }
+
+        assert!(matches!(open(&key, &sealed[..30]), Err(Error::Decrypt)));
+        assert!(matches!(open(&key, b"3   4\n"), Err(Error::Decrypt)));
+    }
+
+    #[test]
+    fn reads_encrypted_copies() {
+        let dir = env::temp_dir().join(format!("aoc-encryption-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.txt");
+        let sealed_path = encrypted_path(&path.to_string_lossy());
+
+        let key = Key::generate();
+        let read = |path: &Path| read_to_string_with(path, || Ok(key.clone()));
+
+        // plain text files are read without a key.
+        fs::write(&path, "plain").unwrap();
+        assert_eq!(
+            read_to_string_with(&path, || Err(Error::MissingKey)).unwrap(),
+            "plain"
+        );
+
+        // the encrypted copy is read if the plain text file is missing or an empty placeholder.
+        fs::write(&sealed_path, seal(&key, b"sealed")).unwrap();
+        assert_eq!(read(&path).unwrap(), "plain");
+        fs::write(&path, "").unwrap();
+        assert_eq!(read(&path).unwrap(), "sealed");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read(&path).unwrap(), "sealed");
+
+        let mut tampered = fs::read(&sealed_path).unwrap();
+        *tampered.last_mut().unwrap() ^= 1;
+        fs::write(&sealed_path, tampered).unwrap();
+        assert!(matches!(read(&path), Err(Error::Decrypt)));
+
+        // without either file, reading fails like for a plain text file. An empty file stays empty.
+        fs::remove_file(&sealed_path).unwrap();
+        assert!(matches!(read(&path), Err(Error::IO(_))));
+        fs::write(&path, "").unwrap();
+        assert_eq!(read(&path).unwrap(), "");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,153 @@
+/// Module that caches downloaded inputs, so that they are only fetched once per year and day.
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::get_year;
+use crate::template::util::checksum;
+use crate::template::Day;
+
+static CACHE_DIR: &str = "./data/cache";
+static CHECKSUMS_FILE_PATH: &str = "./data/cache/checksums.json";
+
+/// Checksums of the cached inputs, keyed by `<year>/<day>`.
+#[derive(Clone, Debug, Default)]
+pub struct InputCache {
+    pub checksums: HashMap<String, String>,
+}
+
+/// Key of a day in the cache. Without `AOC_YEAR`, aoc-cli downloads the latest event.
+pub fn cache_key(day: Day) -> String {
+    let year = get_year().map_or_else(|| "latest".into(), |year| year.to_string());
+    format!("{year}/{day}")
+}
+
+/// Whether a downloaded input is the placeholder that is served without a valid session.
+pub fn is_logged_out_response(input: &str) -> bool {
+    input.contains("Please log in")
+}
+
+impl InputCache {
+    /// Dehydrate the checksums to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        fs::create_dir_all(CACHE_DIR)?;
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(CHECKSUMS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the checksums from a JSON file. If not present, returns an empty cache.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(CHECKSUMS_FILE_PATH) else {
+            return InputCache::default();
+        };
+
+        match InputCache::try_from(s) {
+            Ok(cache) => cache,
+            Err(e) => {
+                eprintln!("{CHECKSUMS_FILE_PATH}: {e}");
+                InputCache::default()
+            }
+        }
+    }
+
+    /// Path of the cached input with the given key.
+    pub fn path(key: &str) -> String {
+        format!("{CACHE_DIR}/{key}.txt")
+    }
+
+    /// Path that a fresh download is written to before it is verified.
+    pub fn download_path(key: &str) -> String {
+        format!("{CACHE_DIR}/{key}.download")
+    }
+
+    /// Read a cached input. Inputs whose checksum does not match are treated as missing.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let expected = self.checksums.get(key)?;
+        let input = fs::read_to_string(Self::path(key)).ok()?;
+        (checksum(input.as_bytes()) == *expected).then_some(input)
+    }
+
+    /// Add an input to the cache.
+    pub fn insert(&mut self, key: &str, input: &str) -> Result<(), io::Error> {
+        let path = Self::path(key);
+        if let Some((dir, _)) = path.rsplit_once('/') {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, input)?;
+        self.checksums
+            .insert(key.to_string(), checksum(input.as_bytes()));
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<InputCache> for JsonValue {
+    fn from(value: InputCache) -> Self {
+        JsonValue::Object(
+            value
+                .checksums
+                .into_iter()
+                .map(|(key, checksum)| (key, JsonValue::String(checksum)))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<String> for InputCache {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let checksums = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .iter()
+            .map(|(key, checksum)| {
+                checksum
+                    .get::<String>()
+                    .map(|checksum| (key.clone(), checksum.clone()))
+                    .ok_or(format!("expected checksum of `{key}` to be a string."))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(InputCache { checksums })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_logged_out_response, InputCache};
+
+    #[test]
+    fn handles_json_checksums() {
+        let json = r#"{ "2024/01": "af63bd4c8601b7be" }"#.to_string();
+        let cache = InputCache::try_from(json).unwrap();
+        assert_eq!(
+            cache.checksums.get("2024/01"),
+            Some(&"af63bd4c8601b7be".to_string())
+        );
+        assert!(InputCache::try_from(r#"{ "2024/01": 1 }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_checksums() {
+        let mut cache = InputCache::default();
+        cache
+            .checksums
+            .insert("1900/01".into(), "0000000000000000".into());
+        assert_eq!(cache.get("1900/01"), None);
+        assert_eq!(cache.get("1900/02"), None);
+    }
+
+    #[test]
+    fn detects_logged_out_response() {
+        assert!(is_logged_out_response(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        ));
+        assert!(!is_logged_out_response("3   4\n4   3\n"));
+    }
+}
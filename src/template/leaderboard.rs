@@ -0,0 +1,436 @@
+/// Module that reads and scores a private leaderboard from its JSON export.
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::{Display, Write},
+    fs,
+    io::Write as _,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{all_days, format_secs, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Timestamps at which a member got the stars of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    pub day: Day,
+    pub part_1: Option<i64>,
+    pub part_2: Option<i64>,
+}
+
+impl Completion {
+    /// Seconds between getting the first and the second star.
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.part_2? - self.part_1?)
+    }
+}
+
+/// A single member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: String,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    pub completions: Vec<Completion>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn completion(&self, day: Day) -> Option<&Completion> {
+        self.completions.iter().find(|c| c.day == day)
+    }
+}
+
+/// A private leaderboard, as exported by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Clone, Debug)]
+pub struct Leaderboard {
+    pub event: u16,
+    pub members: Vec<Member>,
+}
+
+/// Rules to compute the score of a member with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scoring {
+    /// The rules of the website: for every star, the first member to get it receives as many
+    /// points as there are members, the second one point less, and so on.
+    Local,
+    /// One point per star.
+    Stars,
+    /// Like `Local`, but members are ranked by the time between part one and part two of a day.
+    Delta,
+}
+
+impl Leaderboard {
+    /// Compute the score of every member, in the order of `self.members`.
+    pub fn scores(&self, scoring: Scoring) -> Vec<u64> {
+        let mut scores = vec![0; self.members.len()];
+
+        let mut award = |mut ranked: Vec<(usize, i64)>| {
+            ranked.sort_unstable_by_key(|(i, ts)| (*ts, *i));
+            for (rank, (i, _)) in ranked.into_iter().enumerate() {
+                scores[i] += (self.members.len() - rank) as u64;
+            }
+        };
+
+        for day in all_days() {
+            let completions = || {
+                self.members
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, m)| m.completion(day).map(|c| (i, c)))
+            };
+
+            match scoring {
+                Scoring::Local => {
+                    award(
+                        completions()
+                            .filter_map(|(i, c)| Some((i, c.part_1?)))
+                            .collect(),
+                    );
+                    award(
+                        completions()
+                            .filter_map(|(i, c)| Some((i, c.part_2?)))
+                            .collect(),
+                    );
+                }
+                Scoring::Delta => {
+                    award(
+                        completions()
+                            .filter_map(|(i, c)| Some((i, c.delta()?)))
+                            .collect(),
+                    );
+                }
+                Scoring::Stars => {}
+            }
+        }
+
+        if scoring == Scoring::Stars {
+            for (score, member) in scores.iter_mut().zip(&self.members) {
+                *score = member
+                    .completions
+                    .iter()
+                    .map(|c| u64::from(c.part_1.is_some()) + u64::from(c.part_2.is_some()))
+                    .sum();
+            }
+        }
+
+        scores
+    }
+
+    /// Members with their score, sorted from first to last place.
+    pub fn standings(&self, scoring: Scoring) -> Vec<(&Member, u64)> {
+        let mut standings: Vec<_> = self.members.iter().zip(self.scores(scoring)).collect();
+        standings.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.display_name().cmp(&b.display_name()))
+        });
+        standings
+    }
+
+    /// Unix timestamp of the moment the puzzle of `day` was unlocked.
+    pub fn unlock_time(&self, day: Day) -> i64 {
+        day.unlock_time(self.event)
+    }
+}
+
+/// Download the JSON export of a private leaderboard to `path`.
+/// The session cookie is read from the same locations as `aoc-cli` does.
+pub fn fetch(id: &str, year: u16, path: &str) -> Result<(), String> {
+    let session = env::var("ADVENT_OF_CODE_SESSION")
+        .ok()
+        .or_else(|| {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            fs::read_to_string(format!("{home}/.adventofcode.session")).ok()
+        })
+        .map(|s| s.trim().to_string())
+        .ok_or("no session cookie found, see the aoc-cli setup instructions.")?;
+
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    // the cookie is passed in a config on stdin, so that it does not show up in the process list.
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--fail", "--config", "-"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|_| "command \"curl\" not found or not callable.")?;
+
+    let mut stdin = curl
+        .stdin
+        .take()
+        .ok_or("could not pass the session cookie to curl.")?;
+    writeln!(stdin, "cookie = \"session={session}\"").map_err(|e| e.to_string())?;
+    drop(stdin);
+
+    let output = curl.wait_with_output().map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!("could not download \"{url}\"."));
+    }
+
+    let body = String::from_utf8_lossy(&output.stdout);
+    Leaderboard::try_from(body.to_string())?;
+    fs::write(path, body.as_bytes()).map_err(|e| e.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Render the standings of the leaderboard.
+pub fn render_standings(leaderboard: &Leaderboard, scoring: Scoring) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "{ANSI_BOLD}Rank  Score  Stars  Member{ANSI_RESET}");
+
+    for (rank, (member, score)) in leaderboard.standings(scoring).iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "{:>4}  {score:>5}  {:>5}  {}",
+            rank + 1,
+            member.stars,
+            member.display_name()
+        );
+    }
+
+    out
+}
+
+/// Render when every member got their stars on `day`, relative to the unlock of the puzzle.
+pub fn render_day(leaderboard: &Leaderboard, day: Day) -> String {
+    let mut out = String::new();
+    let unlock = leaderboard.unlock_time(day);
+
+    let mut completions: Vec<_> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| m.completion(day).map(|c| (m, c)))
+        .collect();
+
+    if completions.is_empty() {
+        return out;
+    }
+
+    completions.sort_by_key(|(_, c)| (c.part_2.is_none(), c.part_2, c.part_1));
+
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Day {day}    Part 1    Part 2     Delta  Member{ANSI_RESET}"
+    );
+
+    for (member, completion) in completions {
+        let time = |ts: Option<i64>| format_secs(ts.map(|ts| ts - unlock));
+        let _ = writeln!(
+            out,
+            "        {:>8}  {:>8}  {:>8}  {}",
+            time(completion.part_1),
+            time(completion.part_2),
+            format_secs(completion.delta()),
+            member.display_name()
+        );
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let event = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse().ok())
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?
+            .iter()
+            .map(|(id, member)| Member::try_from((id.as_str(), member)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(Leaderboard { event, members })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+impl TryFrom<(&str, &JsonValue)> for Member {
+    type Error = String;
+
+    fn try_from((id, value): (&str, &JsonValue)) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or(format!("expected member {id} to be an object."))?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("expected member {id} to have a numeric `{key}`."))
+        };
+
+        let levels = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or(format!(
+                "expected member {id} to have `completion_day_level`."
+            ))?;
+
+        let completions = all_days()
+            .filter_map(|day| {
+                let parts = levels
+                    .get(&day.into_inner().to_string())?
+                    .get::<HashMap<String, JsonValue>>()?;
+
+                let timestamp = |part: &str| {
+                    parts
+                        .get(part)?
+                        .get::<HashMap<String, JsonValue>>()?
+                        .get("get_star_ts")?
+                        .get::<f64>()
+                        .map(|ts| *ts as i64)
+                };
+
+                Some(Completion {
+                    day,
+                    part_1: timestamp("1"),
+                    part_2: timestamp("2"),
+                })
+            })
+            .collect();
+
+        Ok(Member {
+            id: id.to_string(),
+            name: json.get("name").and_then(|v| v.get::<String>()).cloned(),
+            local_score: number("local_score")? as u64,
+            stars: number("stars")? as u64,
+            completions,
+        })
+    }
+}
+
+impl FromStr for Scoring {
+    type Err = ScoringFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "local" => Ok(Scoring::Local),
+            "stars" => Ok(Scoring::Stars),
+            "delta" => Ok(Scoring::Delta),
+            s => Err(ScoringFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Scoring`].
+#[derive(Debug)]
+pub struct ScoringFromStrError(String);
+
+impl Error for ScoringFromStrError {}
+
+impl Display for ScoringFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid scoring `{}`, expecting one of `local`, `stars`, `delta`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Leaderboard, Scoring};
+    use crate::day;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        let json = r#"{
+            "event": "2024",
+            "owner_id": 1,
+            "members": {
+                "1": {
+                    "id": 1, "name": "ferris", "stars": 4, "local_score": 5, "global_score": 0,
+                    "last_star_ts": 1733116000,
+                    "completion_day_level": {
+                        "1": { "1": { "get_star_ts": 1733029300, "star_index": 1 }, "2": { "get_star_ts": 1733030000, "star_index": 2 } },
+                        "2": { "1": { "get_star_ts": 1733115700, "star_index": 5 }, "2": { "get_star_ts": 1733116000, "star_index": 6 } }
+                    }
+                },
+                "2": {
+                    "id": 2, "name": null, "stars": 3, "local_score": 6, "global_score": 0,
+                    "last_star_ts": 1733115650,
+                    "completion_day_level": {
+                        "1": { "1": { "get_star_ts": 1733029250, "star_index": 3 }, "2": { "get_star_ts": 1733029400, "star_index": 4 } },
+                        "2": { "1": { "get_star_ts": 1733115650, "star_index": 7 } }
+                    }
+                }
+            }
+        }"#;
+
+        Leaderboard::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.event, 2024);
+        assert_eq!(leaderboard.members.len(), 2);
+
+        let anonymous = &leaderboard.members[1];
+        assert_eq!(anonymous.display_name(), "(anonymous user #2)");
+        assert_eq!(anonymous.completion(day!(2)).unwrap().part_2, None);
+        assert_eq!(anonymous.completion(day!(1)).unwrap().delta(), Some(150));
+    }
+
+    #[test]
+    fn computes_unlock_time() {
+        let leaderboard = get_mock_leaderboard();
+        // 2024-12-01T05:00:00Z
+        assert_eq!(leaderboard.unlock_time(day!(1)), 1_733_029_200);
+        assert_eq!(leaderboard.unlock_time(day!(2)), 1_733_115_600);
+    }
+
+    #[test]
+    fn recomputes_scores() {
+        let leaderboard = get_mock_leaderboard();
+        let scores = |scoring| leaderboard.scores(scoring);
+
+        // the recomputed local score matches the one of the website.
+        assert_eq!(
+            scores(Scoring::Local),
+            leaderboard
+                .members
+                .iter()
+                .map(|m| m.local_score)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(scores(Scoring::Stars), vec![4, 3]);
+        assert_eq!(scores(Scoring::Delta), vec![3, 2]);
+
+        let standings = leaderboard.standings(Scoring::Delta);
+        assert_eq!(standings[0].0.id, "1");
+    }
+}
@@ -4,24 +4,52 @@ pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
+pub use clock::*;
 pub use day::*;
+pub use leaderboard::Scoring;
+pub use selection::*;
+pub use solution::Solution;
 
+mod answers;
+mod clock;
 mod day;
+mod encryption;
+mod input_cache;
+mod leaderboard;
+mod module_template;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod selection;
+mod solution;
+mod stats;
+mod status;
 mod timings;
+mod util;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// If only an encrypted copy of the file exists, it is decrypted (see `cargo inputs`).
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = encryption::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
+}
+
+/// Whether [`read_file`] finds a non-empty file, either in plain text or encrypted.
+#[must_use]
+pub fn has_file(folder: &str, day: Day) -> bool {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let encrypted = encryption::encrypted_path(&filepath.to_string_lossy());
+    [filepath.to_string_lossy().into_owned(), encrypted]
+        .iter()
+        .any(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -32,13 +60,15 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = encryption::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, `parse = <fn>` names a parser that is benchmarked on its own before both parts.
+/// The parser is exposed as the constant `PARSER`, so that `cargo bench` can benchmark it as well.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,11 +80,18 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse $parse, [part_one, 1] [part_two, 2]);
+    };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( parse $parse:expr, )? $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The shared parser named in `solution!`, if any. Its result is discarded.
+        #[allow(dead_code)]
+        pub const PARSER: Option<fn(&str)> = $crate::solution!(@parser $( $parse )?);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
@@ -62,7 +99,17 @@ macro_rules! solution {
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
+            $( run_parse($parse, &input); )?
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    (@parser $parse:expr) => {
+        Some(|input| {
+            ::std::hint::black_box($parse(input));
+        })
+    };
+    (@parser) => {
+        None
+    };
 }
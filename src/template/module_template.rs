@@ -0,0 +1,264 @@
+/// Module that renders the solution templates used by `scaffold`.
+use std::{fs, io, path::Path};
+
+use crate::template::aoc_cli::{get_puzzle_path, get_year};
+use crate::template::Day;
+
+static TEMPLATES_DIR: &str = "./templates";
+
+/// Name of the template that is used if none is specified.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Built-in copy of the default template, used if it was removed from the templates directory.
+const DEFAULT_TEMPLATE_CONTENTS: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+#[derive(Debug)]
+pub enum Error {
+    /// No template with the requested name exists. Contains the available names.
+    NotFound(Vec<String>),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Values that are substituted for the placeholders of a template.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub example_part_1: Option<String>,
+    pub example_part_2: Option<String>,
+}
+
+impl Placeholders {
+    /// Collect placeholder values for a day, reading the title and example answers from
+    /// the puzzle description if it was downloaded already.
+    pub fn collect(day: Day) -> Self {
+        let puzzle = fs::read_to_string(get_puzzle_path(day)).unwrap_or_default();
+        let (example_part_1, example_part_2) = find_example_answers(&puzzle);
+
+        Placeholders {
+            day,
+            year: get_year(),
+            title: find_title(&puzzle),
+            example_part_1,
+            example_part_2,
+        }
+    }
+
+    /// Substitute all placeholders in `template`.
+    ///
+    /// | Placeholder | Value |
+    /// | --- | --- |
+    /// | `%DAY_NUMBER%` | day without padding, e.g. `5` |
+    /// | `%DAY%` | day padded to two digits, e.g. `05` |
+    /// | `%YEAR%` | the value of `AOC_YEAR` |
+    /// | `%TITLE%` | title of the puzzle |
+    /// | `%EXAMPLE_PART_1%`, `%EXAMPLE_PART_2%` | `Some(<answer>)` for the example, or `None` |
+    ///
+    /// The templates return unsigned answers, so a negative example answer is rendered as `None`
+    /// with a TODO comment instead of an expression that does not compile.
+    pub fn render(&self, template: &str) -> String {
+        let example = |answer: &Option<String>| match answer {
+            None => "None".into(),
+            Some(a) if a.starts_with('-') => format!(
+                "None /* TODO: the example answer is {a}, use a signed return type and Some({a}) */"
+            ),
+            Some(a) => format!("Some({a})"),
+        };
+
+        template
+            .replace("%DAY_NUMBER%", &self.day.into_inner().to_string())
+            .replace("%DAY%", &self.day.to_string())
+            .replace(
+                "%YEAR%",
+                &self.year.map(|y| y.to_string()).unwrap_or_default(),
+            )
+            .replace("%TITLE%", self.title.as_deref().unwrap_or("Untitled"))
+            .replace("%EXAMPLE_PART_1%", &example(&self.example_part_1))
+            .replace("%EXAMPLE_PART_2%", &example(&self.example_part_2))
+    }
+}
+
+/// Names of the templates in the templates directory.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? == "txt" {
+                Some(path.file_stem()?.to_string_lossy().to_string())
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if !names.iter().any(|n| n == DEFAULT_TEMPLATE) {
+        names.push(DEFAULT_TEMPLATE.into());
+    }
+
+    names.sort_unstable();
+    names
+}
+
+/// Load the template `name` from the templates directory.
+pub fn load(name: &str) -> Result<String, Error> {
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    match fs::read_to_string(path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if name == DEFAULT_TEMPLATE {
+                Ok(DEFAULT_TEMPLATE_CONTENTS.into())
+            } else {
+                Err(Error::NotFound(available()))
+            }
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Extract the title from a heading like `## --- Day 5: Print Queue ---`.
+fn find_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let (_, rest) = line.split_once("--- Day ")?;
+        let (_, title) = rest.split_once(": ")?;
+        Some(title.trim_end_matches('-').trim().to_string())
+    })
+}
+
+/// The example answers are the last emphasized numbers in code spans, e.g. `` `*143*` ``,
+/// before and after the `--- Part Two ---` heading respectively.
+fn find_example_answers(puzzle: &str) -> (Option<String>, Option<String>) {
+    let (part_1, part_2) = match puzzle.split_once("--- Part Two ---") {
+        Some((part_1, part_2)) => (part_1, Some(part_2)),
+        None => (puzzle, None),
+    };
+
+    (
+        find_last_emphasized_number(part_1),
+        part_2.and_then(find_last_emphasized_number),
+    )
+}
+
+/// The example input is the first code block after the first mention of an example,
+/// or the first code block of the puzzle if the example is not introduced as such.
+#[cfg_attr(not(feature = "today"), allow(dead_code))]
+pub(crate) fn find_example_input(puzzle: &str) -> Option<String> {
+    let part_1 = puzzle.split("--- Part Two ---").next().unwrap_or(puzzle);
+    let introduced = part_1
+        .to_ascii_lowercase()
+        .find("example")
+        .and_then(|index| find_code_block(&part_1[index..]));
+
+    introduced.or_else(|| find_code_block(part_1))
+}
+
+#[cfg_attr(not(feature = "today"), allow(dead_code))]
+fn find_code_block(section: &str) -> Option<String> {
+    let (_, rest) = section.split_once("```")?;
+    let (_, rest) = rest.split_once('\n')?;
+    let (block, _) = rest.split_once("```")?;
+    (!block.trim().is_empty()).then(|| block.to_string())
+}
+
+fn find_last_emphasized_number(section: &str) -> Option<String> {
+    section
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .filter(|code| code.starts_with('*') && code.ends_with('*'))
+        .map(|code| code.trim_matches('*'))
+        .filter(|code| {
+            let digits = code.strip_prefix('-').unwrap_or(code);
+            !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+        })
+        .last()
+        .map(String::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_example_answers, find_example_input, find_title, Placeholders};
+    use crate::day;
+
+    const PUZZLE: &str = "\\--- Day 5: Print Queue ---\n\
+        ----------\n\n\
+        The first update `75,47,61,53,29` is in the right order.\n\
+        Adding these together produces `*143*`.\n\n\
+        \\--- Part Two ---\n\
+        ----------\n\n\
+        Taking these together produces `*123*`, the middle page is `*47*` ... `*-9*`.\n\
+        The answer `*not a number*` is ignored.";
+
+    #[test]
+    fn finds_title() {
+        assert_eq!(find_title(PUZZLE), Some("Print Queue".into()));
+        assert_eq!(find_title(""), None);
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        assert_eq!(
+            find_example_answers(PUZZLE),
+            (Some("143".into()), Some("-9".into()))
+        );
+        assert_eq!(find_example_answers("no answers"), (None, None));
+    }
+
+    #[test]
+    fn finds_example_input() {
+        let puzzle = "Run `cargo solve`:\n\n```\n$ cargo solve\n```\n\n\
+            For example:\n\n```\n3   4\n4   3\n```\n\nThen:\n\n```\nignored\n```\n";
+        assert_eq!(find_example_input(puzzle), Some("3   4\n4   3\n".into()));
+        assert_eq!(
+            find_example_input("Consider:\n```\n1\n```"),
+            Some("1\n".into())
+        );
+        assert_eq!(find_example_input(PUZZLE), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: Some(2024),
+            title: Some("Print Queue".into()),
+            example_part_1: Some("143".into()),
+            example_part_2: None,
+        };
+
+        assert_eq!(
+            placeholders.render("solution!(%DAY_NUMBER%); // %YEAR%-%DAY%: %TITLE%"),
+            "solution!(5); // 2024-05: Print Queue"
+        );
+        assert_eq!(
+            placeholders.render("assert_eq!(result, %EXAMPLE_PART_1%); %EXAMPLE_PART_2%"),
+            "assert_eq!(result, Some(143)); None"
+        );
+
+        let negative = Placeholders {
+            example_part_2: Some("-9".into()),
+            ..placeholders
+        };
+        assert_eq!(
+            negative.render("%EXAMPLE_PART_2%"),
+            "None /* TODO: the example answer is -9, use a signed return type and Some(-9) */"
+        );
+    }
+}
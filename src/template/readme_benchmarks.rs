@@ -1,11 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
 
-use crate::template::timings::Timings;
+use crate::template::aoc_cli::get_year;
+use crate::template::timings::{Allocations, Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CONFIG_FILE_PATH: &str = "./data/benchmarks.json";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -21,8 +24,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
@@ -30,8 +33,9 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Find the section of the readme enclosed by (one or two occurences of) `marker`.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,38 +56,162 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GiB", b as f64 / f64::from(1 << 30)),
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / f64::from(1 << 20)),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / f64::from(1 << 10)),
+        b => format!("{b} B"),
+    }
+}
+
+fn format_allocations(allocations: Option<Allocations>) -> String {
+    allocations.map_or_else(|| "-".into(), |a| format_bytes(a.total_bytes))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn format_samples(samples: Option<u64>) -> String {
+    samples.map_or_else(|| "-".into(), |s| s.to_string())
+}
+
+fn format_delta(timing: &Timing) -> String {
+    match timing.previous_total_nanos {
+        Some(previous) if previous > 0_f64 && timing.total_nanos > 0_f64 => {
+            format!(
+                "{:+.1}%",
+                (timing.total_nanos - previous) / previous * 100_f64
+            )
+        }
+        _ => "-".into(),
+    }
+}
+
+/// Renders `value` relative to `max` as a bar of unicode blocks that is at most `width` wide.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_bar(value: f64, max: f64, width: usize) -> String {
+    const BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    if max <= 0_f64 || value <= 0_f64 {
+        return String::new();
+    }
 
-    for timing in timings.data {
+    let eighths = ((value / max) * (width * 8) as f64).round().max(1_f64) as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    if let Some(block) = (eighths % 8).checked_sub(1) {
+        bar.push(BLOCKS[block]);
+    }
+    bar
+}
+
+fn construct_section(timings: Timings, total_millis: f64, config: &TableConfig) -> Vec<String> {
+    let columns = config.columns_for(&timings);
+    let mut data = timings.data;
+
+    if config.sort == SortOrder::Time {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let max_nanos = data.iter().map(|t| t.total_nanos).fold(0_f64, f64::max);
+
+    let mut header = "| Day |".to_string();
+    let mut separator = "| :---: |".to_string();
+    for column in &columns {
+        header.push_str(&format!(" {} |", column.header()));
+        separator.push_str(column.separator());
+    }
+
+    let mut lines: Vec<String> = vec![header, separator];
+
+    for timing in &data {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let mut line = format!("| [Day {}]({}) |", timing.day.into_inner(), path);
+        for column in &columns {
+            line.push_str(&format!(" {} |", column.cell(timing, max_nanos, config)));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    year_section: Option<(u16, &str)>,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    let section = construct_section(timings, total_millis, config);
+
+    match year_section {
+        Some((year, existing)) => {
+            let mut sections = parse_year_sections(existing, prefix);
+            sections.retain(|(y, _)| *y != year);
+            sections.push((year, section.join("\n")));
+            sections.sort_unstable_by_key(|(year, _)| std::cmp::Reverse(*year));
+
+            let sections: Vec<String> = sections
+                .into_iter()
+                .map(|(year, content)| format!("{prefix}# {year}\n\n{content}"))
+                .collect();
+
+            lines.push(sections.join("\n\n"));
+        }
+        None => lines.extend(section),
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Splits the content of an existing table into its per-year sections.
+fn parse_year_sections(existing: &str, prefix: &str) -> Vec<(u16, String)> {
+    let heading = format!("{prefix}# ");
+    let mut sections: Vec<(u16, Vec<&str>)> = vec![];
+
+    for line in existing.lines() {
+        let year = line
+            .strip_prefix(&heading)
+            .and_then(|year| year.trim().parse::<u16>().ok());
+
+        match (year, sections.last_mut()) {
+            (Some(year), _) => sections.push((year, vec![])),
+            (None, Some((_, lines))) if line != MARKER => lines.push(line),
+            _ => {}
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(year, lines)| (year, lines.join("\n").trim().to_string()))
+        .collect()
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    year: Option<u16>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let existing = s[positions.pos_start..positions.pos_end].to_string();
+    let year_section = year
+        .filter(|_| config.per_year)
+        .map(|year| (year, existing.as_str()));
+    let table = construct_table("##", timings, total_millis, config, year_section);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,36 +220,238 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let config = TableConfig::read_from_file();
+    update_content(&mut readme, timings, total_millis, &config, get_year())?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/* -------------------------------------------------------------------------- */
+
+/// A column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    Total,
+    Allocations,
+    Samples,
+    Delta,
+    Chart,
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Allocations => "Allocations",
+            Column::Samples => "Samples",
+            Column::Delta => "Delta",
+            Column::Chart => "Chart",
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        match self {
+            // NOTE: keeps the spacing of the original table to avoid needless readme diffs.
+            Column::Part2 => " :---:  |",
+            Column::Chart => " :--- |",
+            _ => " :---: |",
+        }
+    }
+
+    fn cell(self, timing: &Timing, max_nanos: f64, config: &TableConfig) -> String {
+        let code = |value: Option<&String>| format!("`{}`", value.map_or("-", String::as_str));
+
+        match self {
+            Column::Parse => code(timing.parse.as_ref()),
+            Column::Part1 => code(timing.part_1.as_ref()),
+            Column::Part2 => code(timing.part_2.as_ref()),
+            Column::Total => {
+                code(Some(&format_nanos(timing.total_nanos)).filter(|_| timing.total_nanos > 0_f64))
+            }
+            Column::Allocations => format!(
+                "`{}` / `{}`",
+                format_allocations(timing.part_1_allocs),
+                format_allocations(timing.part_2_allocs)
+            ),
+            Column::Samples => format!(
+                "{} / {}",
+                format_samples(timing.part_1_samples),
+                format_samples(timing.part_2_samples)
+            ),
+            Column::Delta => format_delta(timing),
+            Column::Chart => format_bar(timing.total_nanos, max_nanos, config.chart_width),
+        }
+    }
+}
+
+/// The order of the days in the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Time,
+}
+
+/// Layout of the benchmark table, read from `data/benchmarks.json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableConfig {
+    /// Columns to render. If not set, both parts are rendered, plus allocations if any are stored.
+    pub columns: Option<Vec<Column>>,
+    pub sort: SortOrder,
+    /// Width of the bars in the chart column, in characters.
+    pub chart_width: usize,
+    /// Whether to keep a separate section per `AOC_YEAR` in the readme.
+    pub per_year: bool,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            columns: None,
+            sort: SortOrder::Day,
+            chart_width: 20,
+            per_year: false,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Read the table layout from its config file. If not present, returns the default layout.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(CONFIG_FILE_PATH) else {
+            return TableConfig::default();
+        };
+
+        match TableConfig::try_from(s) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{CONFIG_FILE_PATH}: {e}");
+                TableConfig::default()
+            }
+        }
+    }
+
+    fn columns_for(&self, timings: &Timings) -> Vec<Column> {
+        match &self.columns {
+            Some(columns) => columns.clone(),
+            None if timings.has_allocations() => {
+                vec![Column::Part1, Column::Part2, Column::Allocations]
+            }
+            None => vec![Column::Part1, Column::Part2],
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "allocations" => Ok(Column::Allocations),
+            "samples" => Ok(Column::Samples),
+            "delta" => Ok(Column::Delta),
+            "chart" => Ok(Column::Chart),
+            s => Err(format!("unknown column `{s}`.")),
+        }
+    }
+}
+
+impl TryFrom<String> for TableConfig {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut config = TableConfig::default();
+
+        if let Some(columns) = json.get("columns") {
+            config.columns = Some(
+                columns
+                    .get::<Vec<JsonValue>>()
+                    .ok_or("expected `columns` to be an array.")?
+                    .iter()
+                    .map(|column| {
+                        column
+                            .get::<String>()
+                            .ok_or_else(|| "expected `columns` to contain strings.".to_string())
+                            .and_then(|column| Column::from_str(column))
+                    })
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+
+        if let Some(sort) = json.get("sort") {
+            config.sort = match sort.get::<String>().map(String::as_str) {
+                Some("day") => SortOrder::Day,
+                Some("time") => SortOrder::Time,
+                _ => return Err("expected `sort` to be `day` or `time`.".into()),
+            };
+        }
+
+        if let Some(width) = json.get("chart_width") {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                config.chart_width = width
+                    .get::<f64>()
+                    .filter(|w| **w >= 1_f64)
+                    .map(|w| *w as usize)
+                    .ok_or("expected `chart_width` to be a positive number.")?;
+            }
+        }
+
+        if let Some(per_year) = json.get("per_year") {
+            config.per_year = *per_year
+                .get::<bool>()
+                .ok_or("expected `per_year` to be a boolean.")?;
+        }
+
+        Ok(config)
+    }
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, SortOrder, TableConfig, MARKER};
+    use crate::{
+        day,
+        template::timings::{Allocations, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::empty(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::empty(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Timing::empty(day!(4))
                 },
             ],
         }
@@ -131,28 +461,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +525,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+            None,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +552,104 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_allocs = Some(Allocations {
+            total_bytes: 276,
+            total_blocks: 3,
+            max_bytes: 232,
+        });
+        timings.data[0].part_2_allocs = Some(Allocations {
+            total_bytes: 3 * 1024 * 1024 / 2,
+            total_blocks: 12,
+            max_bytes: 1024,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default(), None).unwrap();
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Allocations |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `276 B` / `1.5 MiB` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` / `-` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn parses_config() {
+        let json = r#"{ "columns": ["part_1", "total", "chart"], "sort": "time", "chart_width": 10, "per_year": true }"#;
+        let config = TableConfig::try_from(json.to_string()).unwrap();
+        assert_eq!(
+            config.columns,
+            Some(vec![Column::Part1, Column::Total, Column::Chart])
+        );
+        assert_eq!(config.sort, SortOrder::Time);
+        assert_eq!(config.chart_width, 10);
+        assert_eq!(config.per_year, true);
+
+        let json = r#"{ "columns": ["part_3"] }"#;
+        assert!(TableConfig::try_from(json.to_string()).is_err());
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].previous_total_nanos = Some(5e+10);
+        timings.data[1].part_1_samples = Some(10);
+        timings.data[1].part_2_samples = Some(20);
+
+        let config = TableConfig {
+            columns: Some(vec![
+                Column::Total,
+                Column::Samples,
+                Column::Delta,
+                Column::Chart,
+            ]),
+            sort: SortOrder::Time,
+            chart_width: 3,
+            per_year: false,
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config, Some(2024)).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Total | Samples | Delta | Chart |",
+            "| :---: | :---: | :---: | :---: | :--- |",
+            "| [Day 4](./src/bin/04.rs) | `90.0s` | - / - | - | ███ |",
+            "| [Day 2](./src/bin/02.rs) | `70.0s` | 10 / 20 | +40.0% | ██▍ |",
+            "| [Day 1](./src/bin/01.rs) | `30.0s` | - / - | - | █ |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_sections_of_other_years() {
+        let config = TableConfig {
+            per_year: true,
+            ..TableConfig::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &config, Some(2023)).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config, Some(2024)).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config, Some(2024)).unwrap();
+
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+        assert_eq!(s.matches("### 2024").count(), 1);
+        assert_eq!(s.matches("### 2023").count(), 1);
+        assert_eq!(s.matches("**Total: 190.00ms**").count(), 2);
+        assert!(s.find("### 2024").unwrap() < s.find("### 2023").unwrap());
+    }
 }
@@ -0,0 +1,208 @@
+/// Module that updates the readme with the stars collected so far.
+/// The table mirrors the one maintained by the `aoc-readme-stars` action, but is built from local state.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::get_year;
+use crate::template::leaderboard::Leaderboard;
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, Error};
+use crate::template::Day;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The stars collected on a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// The stars collected over the calendar, ordered by day.
+#[derive(Clone, Debug, Default)]
+pub struct Stars {
+    pub data: Vec<DayStars>,
+}
+
+impl Stars {
+    /// Collect stars from the answers recorded by `solve --submit`.
+    pub fn from_answers(answers: &Answers) -> Self {
+        Stars {
+            data: answers
+                .data
+                .iter()
+                .map(|a| DayStars {
+                    day: a.day,
+                    part_1: a.part_1.is_some(),
+                    part_2: a.part_2.is_some(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Collect the stars of a member from a private leaderboard JSON export.
+    /// If no member id is given, the leaderboard is expected to contain a single member.
+    pub fn from_leaderboard(json: &str, member_id: Option<&str>) -> Result<Self, Error> {
+        let leaderboard = Leaderboard::try_from(json.to_string()).map_err(Error::Parser)?;
+
+        let member = match (member_id, leaderboard.members.as_slice()) {
+            (Some(id), _) => leaderboard
+                .members
+                .iter()
+                .find(|m| m.id == id)
+                .ok_or_else(|| {
+                    Error::Parser(format!("member `{id}` is not on the leaderboard."))
+                })?,
+            (None, [member]) => member,
+            (None, _) => {
+                return Err(Error::Parser(
+                    "leaderboard has several members, please specify one.".into(),
+                ))
+            }
+        };
+
+        Ok(Stars {
+            data: member
+                .completions
+                .iter()
+                .map(|c| DayStars {
+                    day: c.day,
+                    part_1: c.part_1.is_some(),
+                    part_2: c.part_2.is_some(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Total number of stars collected.
+    pub fn count(&self) -> usize {
+        self.data
+            .iter()
+            .map(|d| usize::from(d.part_1) + usize::from(d.part_2))
+            .sum()
+    }
+}
+
+fn star(value: bool) -> &'static str {
+    if value {
+        "⭐"
+    } else {
+        ""
+    }
+}
+
+fn construct_table(prefix: &str, stars: &Stars, year: Option<u16>) -> String {
+    let heading = year.map_or_else(|| "Results".into(), |year| format!("{year} Results"));
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} {heading}"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day_stars in stars.data.iter().filter(|d| d.part_1 || d.part_2) {
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            day_stars.day.into_inner(),
+            get_path_for_bin(day_stars.day),
+            star(day_stars.part_1),
+            star(day_stars.part_2),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total stars:** {}", stars.count()));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, stars: &Stars, year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", stars, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(stars: &Stars) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, stars, get_year())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, DayStars, Stars, MARKER};
+    use crate::day;
+
+    fn get_mock_stars() -> Stars {
+        Stars {
+            data: vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: true,
+                },
+                DayStars {
+                    day: day!(2),
+                    part_1: true,
+                    part_2: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut s = format!("foo\nbar\n{MARKER}\n{MARKER}\nbaz");
+        update_content(&mut s, &get_mock_stars(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | ⭐ | ⭐ |",
+            "| [Day 2](./src/bin/02.rs) | ⭐ |  |",
+            "",
+            "**Total stars:** 3",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn reads_leaderboard() {
+        let json = r#"{
+            "event": "2024",
+            "members": {
+                "42": {
+                    "name": "ferris",
+                    "stars": 3,
+                    "local_score": 3,
+                    "completion_day_level": {
+                        "1": { "1": { "get_star_ts": 1733029200 }, "2": { "get_star_ts": 1733029500 } },
+                        "3": { "1": { "get_star_ts": 1733202000 } }
+                    }
+                }
+            }
+        }"#;
+
+        let stars = Stars::from_leaderboard(json, None).unwrap();
+        assert_eq!(stars.count(), 3);
+        assert_eq!(stars.data[1].day, day!(3));
+        assert_eq!(stars.data[1].part_2, false);
+
+        assert!(Stars::from_leaderboard(json, Some("7")).is_err());
+    }
+}
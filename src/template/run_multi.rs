@@ -1,38 +1,35 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, process,
+    sync::mpsc,
+};
 
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{source_hash, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+/// Run the solutions for a set of days, printing their output in day order.
+/// With more than one job, days are run concurrently and their output is buffered.
+/// Timed runs are always sequential so that benchmarks do not skew each other.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+) -> Option<Timings> {
+    let outputs = run_days(days_to_run, is_release, is_timed, jobs);
+
+    let timings: Vec<Timing> = outputs
+        .into_iter()
+        .filter(|(_, output)| !output.is_empty())
+        .map(|(day, output)| Timing {
+            source_hash: source_hash(day),
+            ..child_commands::parse_exec_time(&output, day)
+        })
+        .collect();
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,10 +43,133 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run the solutions for a set of days like [`run_multi`], returning the lines that each day
+/// wrote to stdout, in day order.
+pub fn run_days(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+) -> Vec<(Day, Vec<String>)> {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let outputs = if is_timed || jobs <= 1 {
+        run_sequential(&days, is_release, is_timed)
+    } else {
+        run_parallel(&days, is_release, jobs)
+    };
+
+    days.into_iter().zip(outputs).collect()
+}
+
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Reports a day whose solution could not be run, the remaining days are run regardless.
+fn print_failure(error: &Error) {
+    eprintln!("Failed to run the solution: {error:?}");
+    println!("Failed.");
+}
+
+fn run_sequential(days: &[Day], is_release: bool, is_timed: bool) -> Vec<Vec<String>> {
+    days.iter()
+        .enumerate()
+        .map(|(i, day)| {
+            print_header(*day, i > 0);
+
+            match child_commands::run_solution(*day, is_timed, is_release) {
+                Ok(output) => {
+                    if output.is_empty() {
+                        println!("Not solved.");
+                    }
+                    output
+                }
+                Err(e) => {
+                    print_failure(&e);
+                    vec![]
+                }
+            }
+        })
+        .collect()
+}
+
+fn run_parallel(days: &[Day], is_release: bool, jobs: usize) -> Vec<Vec<String>> {
+    // build all solutions upfront so that child invocations do not contend for the build lock.
+    match child_commands::build_solutions(is_release) {
+        Ok(()) => {}
+        Err(Error::BuildFailed(status)) => {
+            eprintln!("Building the solutions failed ({status}), see the errors above.");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Could not build the solutions: {e:?}");
+            process::exit(1);
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+
+    for (i, day) in days.iter().copied().enumerate() {
+        let tx = tx.clone();
+        pool.spawn(move || {
+            let output = child_commands::capture_solution(day, is_release);
+            tx.send((i, output)).unwrap();
+        });
+    }
+
+    drop(tx);
+
+    let mut outputs = Vec::with_capacity(days.len());
+    let mut pending = BTreeMap::new();
+
+    // print buffered output as soon as all preceding days have been printed.
+    for (i, output) in rx {
+        pending.insert(i, output);
+
+        while let Some(output) = pending.remove(&outputs.len()) {
+            print_header(days[outputs.len()], !outputs.is_empty());
+
+            let child_commands::CapturedOutput { stdout, stderr } = match output {
+                Ok(output) => output,
+                Err(e) => {
+                    print_failure(&e);
+                    outputs.push(vec![]);
+                    continue;
+                }
+            };
+
+            stdout.iter().for_each(|line| println!("{line}"));
+            stderr.iter().for_each(|line| eprintln!("{line}"));
+
+            if stdout.is_empty() {
+                println!("Not solved.");
+            }
+
+            outputs.push(stdout);
+        }
+    }
+
+    outputs
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// `cargo build` exited with a failure.
+    BuildFailed(process::ExitStatus),
     IO(io::Error),
 }
 
@@ -68,7 +188,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{timings::Allocations, Day, ANSI_BOLD, ANSI_RESET};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,13 +196,13 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Output of a solution bin, buffered until it has finished.
+    pub struct CapturedOutput {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+    }
 
+    fn solution_command(day: Day, is_timed: bool, is_release: bool) -> Command {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
@@ -96,14 +216,68 @@ pub mod child_commands {
             args.push("--time");
         }
 
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+        cmd
+    }
+
+    /// Run the solution bin for a given day
+    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(vec![]);
+        }
+
+        run_and_capture(&mut solution_command(day, is_timed, is_release))
+    }
+
+    /// Run the solution bin for a given day without forwarding its output.
+    pub fn capture_solution(day: Day, is_release: bool) -> Result<CapturedOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(CapturedOutput {
+                stdout: vec![],
+                stderr: vec![],
+            });
+        }
+
+        let output = solution_command(day, false, is_release).output()?;
+
+        let to_lines = |bytes: &[u8]| {
+            String::from_utf8_lossy(bytes)
+                .lines()
+                .map(String::from)
+                .collect()
+        };
+
+        Ok(CapturedOutput {
+            stdout: to_lines(&output.stdout),
+            stderr: to_lines(&output.stderr),
+        })
+    }
+
+    /// Build all solution bins.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let status = Command::new("cargo").args(&args).status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed(status))
+        }
+    }
+
+    /// Run a command and collect the lines it writes to stdout.
+    pub fn run_and_capture(cmd: &mut Command) -> Result<Vec<String>, Error> {
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
@@ -129,12 +303,7 @@ pub mod child_commands {
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        let mut timings = super::Timing::empty(day);
 
         output
             .iter()
@@ -149,13 +318,19 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_samples(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_samples = samples;
+                } else if part.contains("Parse") {
+                    // NOTE: parsing is part of the solutions, so it does not count towards the total.
+                    timings.parse = Some(timing_str.into());
+                    return;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,17 +339,97 @@ pub mod child_commands {
         timings
     }
 
+    /// Parse the results of both parts from the output of a solution, `None` for a part without
+    /// a result. Results that span multiple lines are joined with line breaks.
+    pub fn parse_answers(output: &[String]) -> (Option<String>, Option<String>) {
+        let mut answers = (None, None);
+
+        for (i, line) in output.iter().enumerate() {
+            let (answer, result) = if let Some(result) = line.strip_prefix("Part 1: ") {
+                (&mut answers.0, result)
+            } else if let Some(result) = line.strip_prefix("Part 2: ") {
+                (&mut answers.1, result)
+            } else {
+                continue;
+            };
+
+            *answer = if result.starts_with('✖') {
+                None
+            } else if result.starts_with('▼') {
+                let lines = output[i + 1..]
+                    .iter()
+                    .take_while(|l| !l.starts_with("Part "))
+                    .map(String::as_str);
+                Some(lines.collect::<Vec<_>>().join("\n"))
+            } else {
+                result
+                    .strip_prefix(ANSI_BOLD)
+                    .and_then(|result| result.split_once(ANSI_RESET))
+                    .map(|(result, _)| result.to_string())
+            };
+        }
+
+        answers
+    }
+
+    /// Parse the heap profiles printed by solutions built with the `dhat-heap` feature.
+    pub fn parse_allocations(output: &[String]) -> (Option<Allocations>, Option<Allocations>) {
+        let mut allocations = (None, None);
+
+        output
+            .iter()
+            .filter_map(|l| {
+                let (part, summary) = l.split_once(" heap: ")?;
+                let Some(parsed) = parse_allocation_summary(summary) else {
+                    eprintln!("Could not parse allocations from line: {l}");
+                    return None;
+                };
+                Some((part, parsed))
+            })
+            .for_each(|(part, parsed)| {
+                if part.contains("Part 1") {
+                    allocations.0 = Some(parsed);
+                } else if part.contains("Part 2") {
+                    allocations.1 = Some(parsed);
+                }
+            });
+
+        allocations
+    }
+
+    fn parse_allocation_summary(summary: &str) -> Option<Allocations> {
+        // format: "<total_bytes> bytes in <total_blocks> blocks, <max_bytes> bytes at peak"
+        let (total, peak) = summary.split_once(", ")?;
+        let (total_bytes, total_blocks) = total.split_once(" bytes in ")?;
+
+        Some(Allocations {
+            total_bytes: total_bytes.trim().parse().ok()?,
+            total_blocks: total_blocks.strip_suffix(" blocks")?.parse().ok()?,
+            max_bytes: peak.strip_suffix(" bytes at peak")?.trim().parse().ok()?,
+        })
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
     fn parse_time(line: &str) -> Option<(&str, f64)> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
-            .split('(')
-            .last()?
+            .rsplit('(')
+            .next()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +460,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_allocations, parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -222,6 +477,23 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: done (1.5ms @ 523 samples)".into(),
+                    "Part 1: 0 (2ms @ 100 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000000_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.part_1.unwrap(), "2ms");
         }
 
         #[test]
@@ -253,5 +525,41 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let (part_1, part_2) = parse_answers(&[
+                "Parse: done (1.5ms)".into(),
+                "Part 1: \x1b[1m-42\x1b[0m (74.13ns)".into(),
+                "Part 2: ✖             ".into(),
+                "".into(),
+            ]);
+            assert_eq!(part_1.as_deref(), Some("-42"));
+            assert_eq!(part_2, None);
+
+            let (part_1, part_2) = parse_answers(&[
+                "Part 1: ▼  (2ms)".into(),
+                "#.#".into(),
+                ".#.".into(),
+                "Part 2: \x1b[1m7\x1b[0m (1ms)".into(),
+            ]);
+            assert_eq!(part_1.as_deref(), Some("#.#\n.#."));
+            assert_eq!(part_2.as_deref(), Some("7"));
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let (part_1, part_2) = parse_allocations(&[
+                "Part 1: 42 (4.1ms)".into(),
+                "Part 1 heap: 276 bytes in 3 blocks, 232 bytes at peak".into(),
+                "Part 2: ✖        ".into(),
+                "".into(),
+            ]);
+            let part_1 = part_1.unwrap();
+            assert_eq!(part_1.total_bytes, 276);
+            assert_eq!(part_1.total_blocks, 3);
+            assert_eq!(part_1.max_bytes, 232);
+            assert_eq!(part_2.is_none(), true);
+        }
     }
 }
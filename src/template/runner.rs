@@ -1,4 +1,5 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
@@ -6,38 +7,107 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::memo::{self, MemoStats};
+use crate::template::answers::Answers;
+use crate::template::readme_stars::{self, Stars};
+use crate::template::stats::{self, Event};
+use crate::template::timings::Allocations;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // drop what earlier code reported, then keep the statistics of the first run.
+    memo::take_stats();
+    let memo_stats = RefCell::new(Vec::new());
+
+    let (result, duration, samples, allocations) = run_timed(func, input, |result| {
+        *memo_stats.borrow_mut() = memo::take_stats();
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if let Some(allocations) = allocations {
+        print_allocations(&allocations, &part_str);
+    }
+
+    if env::args().any(|x| x == "--verbose") {
+        print_memo_stats(&memo_stats.into_inner(), &part_str);
+    }
+
     if let Some(result) = result {
-        submit_result(result, day, part);
+        let answer = result.to_string();
+        if let Some(Ok(output)) = submit_result(&answer, day, part) {
+            if aoc_cli::is_correct_answer(&output) {
+                record_answer(day, part, &answer);
+            }
+        }
     }
 }
 
+/// Store an answer that was accepted by the website and refresh the stars table.
+fn record_answer(day: Day, part: u8, answer: &str) {
+    stats::record_now(day, Event::Solved(part));
+
+    let answers = Answers::read_from_file().record(day, part, answer);
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answer: {e}");
+        return;
+    }
+
+    if readme_stars::update(&Stars::from_answers(&answers)).is_err() {
+        eprintln!("Failed to update the stars table.");
+    }
+}
+
+/// Run the shared parser of a solution, so that its timing can be reported separately.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I) {
+    let print = |duration_str: &str| print!("Parse: {ANSI_ITALIC}done{ANSI_RESET}{duration_str}");
+
+    let (_, duration, samples, _) = run_timed(func, input, |_| print(""));
+
+    print!("\r");
+    print(&format_duration(&duration, samples));
+    println!();
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When built with the `dhat-heap` feature, the heap profile of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<Allocations>) {
     let timer = Instant::now();
-    let result = {
+    let (result, allocations) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        memo::clear_caches();
+        let result = func(input);
+
+        #[cfg(feature = "dhat-heap")]
+        let allocations = {
+            let stats = dhat::HeapStats::get();
+            Some(Allocations {
+                total_bytes: stats.total_bytes,
+                total_blocks: stats.total_blocks,
+                max_bytes: stats.max_bytes as u64,
+            })
+        };
+
+        #[cfg(not(feature = "dhat-heap"))]
+        let allocations = None;
+
+        (result, allocations)
     };
     let base_time = timer.elapsed();
 
@@ -49,7 +119,7 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, allocations)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -66,6 +136,7 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
+        memo::clear_caches();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
@@ -94,6 +165,19 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn print_allocations(allocations: &Allocations, part: &str) {
+    println!(
+        "{part} heap: {} bytes in {} blocks, {} bytes at peak",
+        allocations.total_bytes, allocations.total_blocks, allocations.max_bytes
+    );
+}
+
+fn print_memo_stats(reports: &[(&str, MemoStats)], part: &str) {
+    for (name, stats) in reports {
+        println!("{part} memo {ANSI_ITALIC}{name}{ANSI_RESET}: {stats}");
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
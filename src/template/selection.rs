@@ -0,0 +1,218 @@
+/// Parses day selection expressions such as `1-5,9,20-` or `incomplete,slow:250`.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day};
+
+/// Threshold of the `slow` selector if none is given, in milliseconds.
+const DEFAULT_SLOW_MILLIS: f64 = 100.0;
+
+/// A single term of a [`DaySelection`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaySelector {
+    /// Every day in the inclusive range.
+    Range(Day, Day),
+    /// Days without a stored result for either part.
+    Unsolved,
+    /// Days without a stored result for both parts.
+    Incomplete,
+    /// Days whose source changed since they were last timed.
+    Changed,
+    /// Days whose stored total time exceeds the threshold in milliseconds.
+    Slow(f64),
+}
+
+/// A set of days, expressed as a comma-separated list of [`DaySelector`] terms.
+///
+/// ```
+/// # use advent_of_code::template::DaySelection;
+/// let selection: DaySelection = "1-5,9,20-,incomplete".parse().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySelection(Vec<DaySelector>);
+
+impl DaySelection {
+    /// Resolves the selection to the set of days it matches, using stored timings for
+    /// the state-based selectors.
+    pub(crate) fn resolve(&self, timings: &Timings) -> HashSet<Day> {
+        all_days()
+            .filter(|day| self.0.iter().any(|term| term.matches(*day, timings)))
+            .collect()
+    }
+}
+
+impl DaySelector {
+    fn matches(&self, day: Day, timings: &Timings) -> bool {
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        match self {
+            DaySelector::Range(start, end) => *start <= day && day <= *end,
+            DaySelector::Unsolved => {
+                timing.is_none_or(|t| t.part_1.is_none() && t.part_2.is_none())
+            }
+            DaySelector::Incomplete => !timings.is_day_complete(day),
+            DaySelector::Changed => timings.has_changed(day),
+            DaySelector::Slow(millis) => {
+                timing.is_some_and(|t| t.total_nanos / 1_000_000_f64 > *millis)
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .map(DaySelector::from_str)
+            .collect::<Result<_, _>>()
+            .map(DaySelection)
+    }
+}
+
+impl FromStr for DaySelector {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || DaySelectionFromStrError(s.to_string());
+
+        let parse_day = |value: &str, default: u8| {
+            if value.is_empty() {
+                Day::new(default).ok_or_else(err)
+            } else {
+                value.parse::<Day>().map_err(|_| err())
+            }
+        };
+
+        match s {
+            "all" => Ok(DaySelector::Range(parse_day("", 1)?, parse_day("", 25)?)),
+            "unsolved" => Ok(DaySelector::Unsolved),
+            "incomplete" => Ok(DaySelector::Incomplete),
+            "changed" => Ok(DaySelector::Changed),
+            "slow" => Ok(DaySelector::Slow(DEFAULT_SLOW_MILLIS)),
+            s if s.starts_with("slow:") => s["slow:".len()..]
+                .parse::<f64>()
+                .ok()
+                .filter(|millis| *millis >= 0.0)
+                .map(DaySelector::Slow)
+                .ok_or_else(err),
+            s => {
+                let (start, end) = match s.split_once('-') {
+                    Some((start, end)) => (parse_day(start, 1)?, parse_day(end, 25)?),
+                    None => {
+                        let day = parse_day(s, 0)?;
+                        (day, day)
+                    }
+                };
+
+                if start > end {
+                    return Err(err());
+                }
+
+                Ok(DaySelector::Range(start, end))
+            }
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError(String);
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days (`5`), ranges (`1-5`, `20-`) \
+            or one of `all`, `unsolved`, `incomplete`, `changed`, `slow[:<ms>]`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DaySelection, DaySelector};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day, part_1: Option<&str>, part_2: Option<&str>, total_nanos| Timing {
+            part_1: part_1.map(String::from),
+            part_2: part_2.map(String::from),
+            total_nanos,
+            ..Timing::empty(day)
+        };
+
+        Timings {
+            data: vec![
+                timing(day!(1), Some("10ms"), Some("20ms"), 3e+7),
+                timing(day!(2), Some("300ms"), None, 3e+8),
+                timing(day!(3), None, None, 0.0),
+            ],
+        }
+    }
+
+    fn resolve(s: &str) -> Vec<u8> {
+        let mut days: Vec<u8> = s
+            .parse::<DaySelection>()
+            .unwrap()
+            .resolve(&get_mock_timings())
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(
+            "5".parse::<DaySelector>().unwrap(),
+            DaySelector::Range(day!(5), day!(5))
+        );
+        assert_eq!(
+            "20-".parse::<DaySelector>().unwrap(),
+            DaySelector::Range(day!(20), day!(25))
+        );
+        assert_eq!(
+            "-3".parse::<DaySelector>().unwrap(),
+            DaySelector::Range(day!(1), day!(3))
+        );
+        assert_eq!(resolve("1-3,9,24-"), vec![1, 2, 3, 9, 24, 25]);
+        assert_eq!(resolve("all").len(), 25);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("5-3".parse::<DaySelection>().is_err());
+        assert!("1,,2".parse::<DaySelection>().is_err());
+        assert!("slow:fast".parse::<DaySelection>().is_err());
+        assert!("tomorrow".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn resolves_timing_selectors() {
+        assert_eq!(resolve("incomplete").len(), 24);
+        assert_eq!(resolve("incomplete").contains(&1), false);
+        assert_eq!(resolve("unsolved").contains(&2), false);
+        assert_eq!(resolve("unsolved").contains(&3), true);
+        assert_eq!(resolve("slow"), vec![2]);
+        assert_eq!(resolve("slow:10"), vec![1, 2]);
+        assert_eq!(resolve("1,slow"), vec![1, 2]);
+    }
+}
@@ -0,0 +1,20 @@
+/// Module with the trait of solutions whose parts share the parsed input.
+use std::fmt::Display;
+
+/// A solution that parses its input once and passes the result to both parts,
+/// see the `shared-parse` template.
+///
+/// The `solution!` macro, the tests and the benchmarks call free `part_one` and `part_two`
+/// functions, so a day that implements this trait delegates to it from those.
+pub trait Solution {
+    /// The parsed input.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_one(input: &Self::Input) -> Option<Self::Answer1>;
+
+    fn part_two(input: &Self::Input) -> Option<Self::Answer2>;
+}
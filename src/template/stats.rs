@@ -0,0 +1,315 @@
+/// Module that keeps track of how long it took to solve each puzzle.
+use std::{collections::HashMap, fmt::Write, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::get_year;
+use crate::template::{format_secs, Clock, Day, SystemClock, ANSI_BOLD, ANSI_RESET};
+
+static STATS_FILE_PATH: &str = "./data/stats.json";
+
+/// A milestone of working on a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The day was scaffolded.
+    Scaffolded,
+    /// The solution of the day was run for the first time.
+    FirstRun,
+    /// The answer to a part was accepted by the website.
+    Solved(u8),
+}
+
+/// Unix timestamps of the milestones of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: Day,
+    pub unlocked: Option<i64>,
+    pub scaffolded: Option<i64>,
+    pub first_run: Option<i64>,
+    pub part_1: Option<i64>,
+    pub part_2: Option<i64>,
+}
+
+impl DayStats {
+    fn event_mut(&mut self, event: Event) -> Option<&mut Option<i64>> {
+        match event {
+            Event::Scaffolded => Some(&mut self.scaffolded),
+            Event::FirstRun => Some(&mut self.first_run),
+            Event::Solved(1) => Some(&mut self.part_1),
+            Event::Solved(2) => Some(&mut self.part_2),
+            Event::Solved(_) => None,
+        }
+    }
+
+    /// Seconds between the unlock of the puzzle and the first scaffold.
+    pub fn start_delay(&self) -> Option<i64> {
+        Some(self.scaffolded? - self.unlocked?)
+    }
+
+    /// Seconds between the start of work on the puzzle and getting the star of `part`.
+    /// Work is considered to start at the first scaffold, or at the unlock if it came later.
+    pub fn solve_time(&self, part: u8) -> Option<i64> {
+        let solved = match part {
+            1 => self.part_1?,
+            2 => self.part_2?,
+            _ => return None,
+        };
+        let start = self.scaffolded.max(self.unlocked)?;
+        Some(solved - start)
+    }
+
+    /// Seconds between getting the first and the second star.
+    pub fn delta(&self) -> Option<i64> {
+        Some(self.part_2? - self.part_1?)
+    }
+}
+
+/// Timestamps of the milestones of every day, can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    pub data: Vec<DayStats>,
+}
+
+impl Stats {
+    /// Dehydrate stats to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(STATS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate stats from a JSON file. If not present, returns empty stats.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(STATS_FILE_PATH) else {
+            return Stats::default();
+        };
+
+        match Stats::try_from(s) {
+            Ok(stats) => stats,
+            Err(e) => {
+                eprintln!("{STATS_FILE_PATH}: {e}");
+                Stats::default()
+            }
+        }
+    }
+
+    /// Record the time of an event. Only the first occurence of an event is kept.
+    pub fn record(&self, day: Day, event: Event, timestamp: i64, year: Option<u16>) -> Self {
+        let mut stats = self.clone();
+
+        if !stats.data.iter().any(|s| s.day == day) {
+            stats.data.push(DayStats {
+                day,
+                unlocked: None,
+                scaffolded: None,
+                first_run: None,
+                part_1: None,
+                part_2: None,
+            });
+            stats.data.sort_unstable_by_key(|s| s.day);
+        }
+
+        for entry in stats.data.iter_mut().filter(|s| s.day == day) {
+            if entry.unlocked.is_none() {
+                entry.unlocked = year.map(|year| day.unlock_time(year));
+            }
+
+            if let Some(time) = entry.event_mut(event) {
+                time.get_or_insert(timestamp);
+            }
+        }
+
+        stats
+    }
+}
+
+/// Records an event of a day at the current time in the stats file.
+/// Failing to do so only warns, since the stats are merely informational.
+pub fn record_now(day: Day, event: Event) {
+    let stats = Stats::read_from_file().record(day, event, SystemClock.now(), get_year());
+
+    if let Err(e) = stats.store_file() {
+        eprintln!("Failed to store solve stats: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Render the solve durations of every tracked day.
+pub fn render(stats: &Stats) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Day     Start delay       Part 1       Part 2   Part 1 → 2{ANSI_RESET}"
+    );
+
+    for entry in &stats.data {
+        let _ = writeln!(
+            out,
+            "{}  {:>12} {:>12} {:>12} {:>12}",
+            entry.day,
+            format_secs(entry.start_delay()),
+            format_secs(entry.solve_time(1)),
+            format_secs(entry.solve_time(2)),
+            format_secs(entry.delta()),
+        );
+    }
+
+    let total: i64 = stats.data.iter().filter_map(|s| s.solve_time(2)).sum();
+    let _ = write!(
+        out,
+        "\n{ANSI_BOLD}Total solve time:{ANSI_RESET} {}\n\
+        Start delay: unlock → scaffold, part times: scaffold (or unlock) → accepted answer.",
+        format_secs(Some(total))
+    );
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Stats> for JsonValue {
+    fn from(value: Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Stats {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Stats {
+            data: json_data
+                .iter()
+                .map(DayStats::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&DayStats> for JsonValue {
+    fn from(value: &DayStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        let timestamps = [
+            ("unlocked", value.unlocked),
+            ("scaffolded", value.scaffolded),
+            ("first_run", value.first_run),
+            ("part_1", value.part_1),
+            ("part_2", value.part_2),
+        ];
+
+        for (key, timestamp) in timestamps {
+            map.insert(
+                key.into(),
+                timestamp.map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected stats.day to be a Day struct.")?;
+
+        let timestamp = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|t| *t as i64)
+        };
+
+        Ok(DayStats {
+            day,
+            unlocked: timestamp("unlocked"),
+            scaffolded: timestamp("scaffolded"),
+            first_run: timestamp("first_run"),
+            part_1: timestamp("part_1"),
+            part_2: timestamp("part_2"),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Event, Stats};
+    use crate::day;
+
+    // 2024-12-01T05:00:00Z
+    const UNLOCK: i64 = 1_733_029_200;
+
+    fn get_mock_stats() -> Stats {
+        Stats::default()
+            .record(day!(1), Event::Scaffolded, UNLOCK + 60, Some(2024))
+            .record(day!(1), Event::FirstRun, UNLOCK + 600, Some(2024))
+            .record(day!(1), Event::FirstRun, UNLOCK + 700, Some(2024))
+            .record(day!(1), Event::Solved(1), UNLOCK + 900, Some(2024))
+            .record(day!(1), Event::Solved(2), UNLOCK + 1500, Some(2024))
+            .record(day!(1), Event::Solved(2), UNLOCK + 3000, Some(2024))
+    }
+
+    #[test]
+    fn records_first_occurence() {
+        let stats = get_mock_stats();
+        let entry = &stats.data[0];
+
+        assert_eq!(entry.unlocked, Some(UNLOCK));
+        assert_eq!(entry.first_run, Some(UNLOCK + 600));
+        assert_eq!(entry.part_2, Some(UNLOCK + 1500));
+    }
+
+    #[test]
+    fn computes_durations() {
+        let stats = get_mock_stats();
+        let entry = &stats.data[0];
+
+        assert_eq!(entry.start_delay(), Some(60));
+        assert_eq!(entry.solve_time(1), Some(840));
+        assert_eq!(entry.solve_time(2), Some(1440));
+        assert_eq!(entry.delta(), Some(600));
+    }
+
+    #[test]
+    fn handles_json_stats() {
+        let stats = get_mock_stats();
+        let json = tinyjson::JsonValue::from(stats.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Stats::try_from(json).unwrap();
+        assert_eq!(parsed.data, stats.data);
+    }
+}
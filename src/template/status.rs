@@ -0,0 +1,280 @@
+/// Module that summarises the state of every day of the calendar.
+use std::fmt::Write;
+use std::path::Path;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::{get_example_path, get_input_path, get_puzzle_path, get_year};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{all_days, encryption, Day, ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// State of a single part of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The day has not been scaffolded yet.
+    Missing,
+    /// The part returned no result when it was last timed.
+    Stub,
+    /// The day has not been timed yet and the part has no recorded answer.
+    Implemented,
+    /// The part returned a result when it was last timed, or its answer was accepted.
+    Solved,
+}
+
+/// Collected state of a single day.
+#[derive(Debug, Clone)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub input: bool,
+    pub example: bool,
+    pub puzzle: bool,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+    /// Whether the answers of all parts were accepted by the website.
+    pub verified: bool,
+    pub total_millis: Option<f64>,
+}
+
+impl DayStatus {
+    /// Collect the status of a day from the file system, stored timings and recorded answers.
+    pub fn collect(day: Day, timings: &Timings, answers: &Answers) -> Self {
+        let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+        let timing = timings.data.iter().find(|t| t.day == day);
+        let answer = answers.get(day);
+
+        // like the stars table, both parts count as verified once their answers are recorded.
+        let part_1 = answer.is_some_and(|a| a.part_1.is_some());
+        let part_2 = answer.is_some_and(|a| a.part_2.is_some());
+
+        DayStatus {
+            day,
+            scaffolded,
+            input: is_non_empty_file(&get_input_path(day))
+                || is_non_empty_file(&encryption::encrypted_path(&get_input_path(day))),
+            example: is_non_empty_file(&get_example_path(day)),
+            puzzle: is_non_empty_file(&get_puzzle_path(day)),
+            part_1: part_status(scaffolded, part_1, timing.map(|t| t.part_1.is_some())),
+            part_2: part_status(scaffolded, part_2, timing.map(|t| t.part_2.is_some())),
+            verified: part_1 && part_2,
+            total_millis: timing
+                .filter(|t| t.total_nanos > 0_f64)
+                .map(|t| t.total_nanos / 1_000_000_f64),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.part_1 == PartStatus::Solved && self.part_2 == PartStatus::Solved
+    }
+
+    fn color(&self) -> &'static str {
+        if !self.scaffolded {
+            ANSI_DIM
+        } else if self.is_complete() {
+            ANSI_GREEN
+        } else if self.part_1 != PartStatus::Solved && self.part_2 != PartStatus::Solved {
+            ANSI_RED
+        } else {
+            ANSI_YELLOW
+        }
+    }
+}
+
+/// Collect the status of every day of advent.
+pub fn collect_all() -> Vec<DayStatus> {
+    let timings = Timings::read_from_file();
+    let answers = Answers::read_from_file();
+    all_days()
+        .map(|day| DayStatus::collect(day, &timings, &answers))
+        .collect()
+}
+
+/// The status of a part, given whether its answer was recorded and whether the last timed run
+/// returned a result (`None` if the day was not timed).
+fn part_status(scaffolded: bool, recorded: bool, timed: Option<bool>) -> PartStatus {
+    match timed {
+        _ if !scaffolded => PartStatus::Missing,
+        _ if recorded => PartStatus::Solved,
+        Some(true) => PartStatus::Solved,
+        Some(false) => PartStatus::Stub,
+        None => PartStatus::Implemented,
+    }
+}
+
+fn is_non_empty_file(path: &str) -> bool {
+    Path::new(path).metadata().is_ok_and(|m| m.len() > 0)
+}
+
+/// Day of the week of december 1st as index from monday (0) to sunday (6).
+fn first_weekday(year: u16) -> usize {
+    NaiveDate::from_ymd_opt(i32::from(year), 12, 1)
+        .map_or(0, |date| date.weekday().num_days_from_monday() as usize)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn mark(value: bool) -> &'static str {
+    if value {
+        "✔"
+    } else {
+        "✖"
+    }
+}
+
+fn part_mark(status: PartStatus) -> &'static str {
+    match status {
+        PartStatus::Missing => "-",
+        PartStatus::Stub => "✖",
+        PartStatus::Implemented => "?",
+        PartStatus::Solved => "★",
+    }
+}
+
+fn format_millis(total_millis: Option<f64>) -> String {
+    total_millis.map_or_else(|| "-".into(), |ms| format!("{ms:.2}ms"))
+}
+
+/// Render the statuses as a colour-coded calendar grid followed by a line per scaffolded day.
+pub fn render_terminal(statuses: &[DayStatus], year: Option<u16>) -> String {
+    let mut out = String::new();
+
+    let title = year.map_or_else(|| "December".into(), |year| format!("December {year}"));
+    let _ = writeln!(out, "{ANSI_BOLD}{title:^34}{ANSI_RESET}");
+    let _ = writeln!(out, " Mo   Tu   We   Th   Fr   Sa   Su");
+
+    let offset = year.map_or(0, first_weekday);
+    let mut line = "     ".repeat(offset);
+
+    for (i, status) in statuses.iter().enumerate() {
+        let _ = write!(line, " {}{}{ANSI_RESET}  ", status.color(), status.day);
+        if (i + offset) % 7 == 6 {
+            let _ = writeln!(out, "{}", line.trim_end());
+            line.clear();
+        }
+    }
+
+    if !line.is_empty() {
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+
+    let _ = writeln!(
+        out,
+        "\n{ANSI_GREEN}complete{ANSI_RESET}  {ANSI_YELLOW}in progress{ANSI_RESET}  \
+        {ANSI_RED}not solved{ANSI_RESET}  {ANSI_DIM}not scaffolded{ANSI_RESET}\n"
+    );
+
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Day  Input  Example  Puzzle  Part 1  Part 2  Verified  Time{ANSI_RESET}"
+    );
+
+    for status in statuses.iter().filter(|s| s.scaffolded) {
+        let _ = writeln!(
+            out,
+            "{}{}{ANSI_RESET}   {}      {}        {}       {}       {}       {}         {}",
+            status.color(),
+            status.day,
+            mark(status.input),
+            mark(status.example),
+            mark(status.puzzle),
+            part_mark(status.part_1),
+            part_mark(status.part_2),
+            mark(status.verified),
+            format_millis(status.total_millis),
+        );
+    }
+
+    let _ = write!(out, "\n★ solved  ? not timed yet  ✖ no result");
+
+    out
+}
+
+/// Render the statuses as a Markdown table.
+pub fn render_markdown(statuses: &[DayStatus]) -> String {
+    let mut lines: Vec<String> = vec![
+        "| Day | Scaffolded | Input | Example | Puzzle | Part 1 | Part 2 | Verified | Time |"
+            .into(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for status in statuses {
+        lines.push(format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+            status.day.into_inner(),
+            mark(status.scaffolded),
+            mark(status.input),
+            mark(status.example),
+            mark(status.puzzle),
+            part_mark(status.part_1),
+            part_mark(status.part_2),
+            mark(status.verified),
+            format_millis(status.total_millis),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Prints the status of the whole calendar.
+pub fn print(markdown: bool) {
+    let statuses = collect_all();
+
+    if markdown {
+        println!("{}", render_markdown(&statuses));
+    } else {
+        println!("{}", render_terminal(&statuses, get_year()));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{first_weekday, part_status, render_markdown, DayStatus, PartStatus};
+    use crate::day;
+
+    #[test]
+    fn derives_part_status() {
+        assert_eq!(part_status(false, true, Some(true)), PartStatus::Missing);
+        assert_eq!(part_status(true, true, None), PartStatus::Solved);
+        assert_eq!(part_status(true, true, Some(false)), PartStatus::Solved);
+        assert_eq!(part_status(true, false, Some(true)), PartStatus::Solved);
+        assert_eq!(part_status(true, false, Some(false)), PartStatus::Stub);
+        assert_eq!(part_status(true, false, None), PartStatus::Implemented);
+    }
+
+    #[test]
+    fn computes_first_weekday() {
+        // december 1st, 2024 was a sunday, 2023 a friday and 2022 a thursday.
+        assert_eq!(first_weekday(2024), 6);
+        assert_eq!(first_weekday(2023), 4);
+        assert_eq!(first_weekday(2022), 3);
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let status = DayStatus {
+            day: day!(14),
+            scaffolded: true,
+            input: true,
+            example: true,
+            puzzle: false,
+            part_1: PartStatus::Solved,
+            part_2: PartStatus::Stub,
+            verified: false,
+            total_millis: Some(1.5),
+        };
+        let table = render_markdown(&[status]);
+        assert_eq!(
+            table.lines().last().unwrap(),
+            "| 14 | ✔ | ✔ | ✔ | ✖ | ★ | ✖ | ✖ | 1.50ms |"
+        );
+    }
+}
@@ -1,10 +1,19 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::util::checksum;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Hash the source of a solution bin, if it exists.
+pub fn source_hash(day: Day) -> Option<String> {
+    fs::read(get_path_for_bin(day))
+        .ok()
+        .map(|source| checksum(&source))
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
@@ -12,6 +21,41 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_allocs: Option<Allocations>,
+    pub part_2_allocs: Option<Allocations>,
+    pub source_hash: Option<String>,
+    pub parse: Option<String>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Total time of the timing this one replaced, used to show deltas.
+    pub previous_total_nanos: Option<f64>,
+}
+
+impl Timing {
+    /// Creates a timing for a day without any recorded data.
+    pub fn empty(day: Day) -> Self {
+        Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            source_hash: None,
+            parse: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            previous_total_nanos: None,
+        }
+    }
+}
+
+/// Represents the heap allocation profile of a single part, as reported by DHAT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub max_bytes: u64,
 }
 
 /// Represents benchmark times for a set of days.
@@ -45,11 +89,23 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Allocation profiles are kept from `self` if `other` does not carry one,
+    /// the total time of overwritten timings is kept as previous total.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.part_1_allocs = timing.part_1_allocs.or(old.part_1_allocs);
+                timing.part_2_allocs = timing.part_2_allocs.or(old.part_2_allocs);
+                timing.previous_total_nanos = if old.total_nanos > 0_f64 {
+                    Some(old.total_nanos)
+                } else {
+                    old.previous_total_nanos
+                };
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +114,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -72,6 +128,49 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Record the allocation profile of a day, keeping its stored timings if present.
+    pub fn with_allocations(
+        &self,
+        day: Day,
+        part_1_allocs: Option<Allocations>,
+        part_2_allocs: Option<Allocations>,
+    ) -> Self {
+        let mut timings = self.clone();
+
+        if !timings.data.iter().any(|t| t.day == day) {
+            timings.data.push(Timing::empty(day));
+            timings.data.sort_unstable_by_key(|a| a.day);
+        }
+
+        for timing in timings.data.iter_mut().filter(|t| t.day == day) {
+            timing.part_1_allocs = part_1_allocs.or(timing.part_1_allocs);
+            timing.part_2_allocs = part_2_allocs.or(timing.part_2_allocs);
+        }
+
+        timings
+    }
+
+    /// Whether the source of a day differs from the source its stored timing was recorded with.
+    /// Days that were never timed count as changed, days that are not scaffolded do not.
+    pub fn has_changed(&self, day: Day) -> bool {
+        let Some(current) = source_hash(day) else {
+            return false;
+        };
+
+        self.data
+            .iter()
+            .find(|t| t.day == day)
+            .and_then(|t| t.source_hash.as_ref())
+            .is_none_or(|stored| *stored != current)
+    }
+
+    /// Whether any stored day carries an allocation profile.
+    pub fn has_allocations(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_allocs.is_some() || t.part_2_allocs.is_some())
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -140,6 +239,46 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "source_hash".into(),
+            value
+                .source_hash
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let samples =
+            |samples: Option<u64>| samples.map_or(JsonValue::Null, |s| JsonValue::Number(s as f64));
+
+        map.insert("part_1_samples".into(), samples(value.part_1_samples));
+        map.insert("part_2_samples".into(), samples(value.part_2_samples));
+
+        map.insert(
+            "previous_total_nanos".into(),
+            value
+                .previous_total_nanos
+                .map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        map.insert(
+            "part_1_allocs".into(),
+            value.part_1_allocs.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_allocs".into(),
+            value.part_2_allocs.map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +312,97 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: all following fields are optional to stay compatible with older timing files.
+        let part_1_allocs = match json.get("part_1_allocs") {
+            Some(v) if !v.is_null() => Some(Allocations::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_allocs = match json.get("part_2_allocs") {
+            Some(v) if !v.is_null() => Some(Allocations::try_from(v)?),
+            _ => None,
+        };
+
+        let source_hash = json
+            .get("source_hash")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
+        let previous_total_nanos = json
+            .get("previous_total_nanos")
+            .and_then(|v| v.get::<f64>().copied());
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_allocs,
+            part_2_allocs,
+            source_hash,
+            parse,
+            part_1_samples: samples("part_1_samples"),
+            part_2_samples: samples("part_2_samples"),
+            previous_total_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Allocations> for JsonValue {
+    fn from(value: Allocations) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "total_blocks".into(),
+                JsonValue::Number(value.total_blocks as f64),
+            );
+            map.insert(
+                "max_bytes".into(),
+                JsonValue::Number(value.max_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Allocations {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocations to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected allocations.{key} to be a number."))
+        };
+
+        Ok(Allocations {
+            total_bytes: get_number("total_bytes")?,
+            total_blocks: get_number("total_blocks")?,
+            max_bytes: get_number("max_bytes")?,
         })
     }
 }
@@ -194,22 +419,22 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::empty(day!(1))
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::empty(day!(2))
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::empty(day!(4))
                 },
             ],
         }
@@ -250,6 +475,18 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        fn handles_json_allocations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "part_1_allocs": { "total_bytes": 276, "total_blocks": 3, "max_bytes": 232 }, "part_2_allocs": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let allocs = timing.part_1_allocs.unwrap();
+            assert_eq!(allocs.total_bytes, 276);
+            assert_eq!(allocs.total_blocks, 3);
+            assert_eq!(allocs.max_bytes, 232);
+            assert_eq!(timing.part_2_allocs, None);
+        }
     }
 
     mod serialization {
@@ -285,49 +522,49 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::empty(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::empty(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    ..Timing::empty(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Allocations, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -337,10 +574,10 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Timing::empty(day!(3))
                 }],
             };
             let merged = timings.merge(&other);
@@ -357,10 +594,10 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Timing::empty(day!(2))
                 }],
             };
             let merged = timings.merge(&other);
@@ -387,5 +624,37 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_previous_totals() {
+            let timings = get_mock_timings();
+            let mut other = Timing::empty(day!(2));
+            other.total_nanos = 1e+10;
+            let merged = timings.merge(&Timings { data: vec![other] });
+            assert_eq!(merged.data[1].total_nanos, 1e+10);
+            assert_eq!(merged.data[1].previous_total_nanos, Some(7e+10));
+            assert_eq!(merged.data[0].previous_total_nanos, None);
+        }
+
+        #[test]
+        fn keeps_stored_allocations() {
+            let allocs = Allocations {
+                total_bytes: 276,
+                total_blocks: 3,
+                max_bytes: 232,
+            };
+            let timings = get_mock_timings().with_allocations(day!(2), Some(allocs), None);
+            let other = Timings {
+                data: vec![Timing {
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    ..Timing::empty(day!(2))
+                }],
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].part_1, Some("1ms".into()));
+            assert_eq!(merged.data[1].part_1_allocs, Some(allocs));
+        }
     }
 }
@@ -0,0 +1,24 @@
+//! Module with small helpers that are shared by several template modules.
+
+/// Hash arbitrary bytes as a hex string.
+/// Uses FNV-1a, which - unlike the std hasher - is stable across toolchains.
+pub fn checksum(bytes: &[u8]) -> String {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::checksum;
+
+    #[test]
+    fn computes_fnv_checksums() {
+        assert_eq!(checksum(b""), "cbf29ce484222325");
+        assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum(b"3   4\n"), checksum(b"3   5\n"));
+    }
+}
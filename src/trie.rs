@@ -0,0 +1,331 @@
+//! A trie of words for puzzles about dictionaries, e.g. arranging towels in 2024 day 19,
+//! and an Aho–Corasick automaton that finds all words in a text in a single pass.
+//!
+//! Both are generic over the symbols of a word, which are bytes for `&str` words.
+use std::collections::VecDeque;
+
+/// The root node of every trie.
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    /// Edges to the child nodes, sorted by symbol.
+    children: Vec<(T, usize)>,
+    /// The id of the word that ends at this node.
+    word: Option<usize>,
+    /// The number of symbols between the root and this node.
+    depth: usize,
+}
+
+impl<T: Ord> Node<T> {
+    fn new(depth: usize) -> Self {
+        Self {
+            children: Vec::new(),
+            word: None,
+            depth,
+        }
+    }
+
+    fn child(&self, symbol: &T) -> Option<usize> {
+        self.children
+            .binary_search_by(|(s, _)| s.cmp(symbol))
+            .ok()
+            .map(|index| self.children[index].1)
+    }
+}
+
+/// A prefix tree whose nodes are stored in a single vector instead of nested maps.
+///
+/// Words get ids in the order in which they are inserted first.
+#[derive(Debug, Clone)]
+pub struct Trie<T = u8> {
+    nodes: Vec<Node<T>>,
+    words: usize,
+}
+
+impl<T: Ord + Copy> Trie<T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(0)],
+            words: 0,
+        }
+    }
+
+    /// Adds a word and returns its id. Inserting a word again returns the id it already has.
+    pub fn insert(&mut self, word: impl AsRef<[T]>) -> usize {
+        let mut node = ROOT;
+        for &symbol in word.as_ref() {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by(|(s, _)| s.cmp(&symbol))
+            {
+                Ok(index) => self.nodes[node].children[index].1,
+                Err(index) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::new(self.nodes[node].depth + 1));
+                    self.nodes[node].children.insert(index, (symbol, child));
+                    child
+                }
+            };
+        }
+
+        *self.nodes[node].word.get_or_insert_with(|| {
+            self.words += 1;
+            self.words - 1
+        })
+    }
+
+    /// Follows `word` from the root, returning the node it ends at.
+    fn walk(&self, word: &[T]) -> Option<usize> {
+        word.iter()
+            .try_fold(ROOT, |node, symbol| self.nodes[node].child(symbol))
+    }
+
+    /// Returns the id of `word`, if it was inserted.
+    pub fn get(&self, word: impl AsRef<[T]>) -> Option<usize> {
+        self.walk(word.as_ref())
+            .and_then(|node| self.nodes[node].word)
+    }
+
+    pub fn contains(&self, word: impl AsRef<[T]>) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// Whether any word starts with `prefix`.
+    pub fn has_prefix(&self, prefix: impl AsRef<[T]>) -> bool {
+        self.walk(prefix.as_ref()).is_some()
+    }
+
+    /// The number of distinct words.
+    pub fn len(&self) -> usize {
+        self.words
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words == 0
+    }
+
+    /// Iterates over the words that `haystack` starts with, from the shortest to the longest.
+    /// Yields the length and the id of each word.
+    pub fn prefixes<'a>(&'a self, haystack: &'a [T]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = Some(ROOT);
+        let empty = self.nodes[ROOT].word.map(|word| (0, word));
+
+        empty.into_iter().chain(
+            haystack
+                .iter()
+                .enumerate()
+                .map_while(move |(index, symbol)| {
+                    node = self.nodes[node?].child(symbol);
+                    Some((index + 1, node?))
+                })
+                .filter_map(|(length, node)| self.nodes[node].word.map(|word| (length, word))),
+        )
+    }
+
+    /// Counts the ways to write `text` as a sequence of words, where words may be repeated.
+    /// The empty text can be written in one way.
+    pub fn count_segmentations(&self, text: impl AsRef<[T]>) -> u64 {
+        let text = text.as_ref();
+        // ways[i] is the number of segmentations of text[i..].
+        let mut ways = vec![0_u64; text.len() + 1];
+        ways[text.len()] = 1;
+
+        for start in (0..text.len()).rev() {
+            ways[start] = self
+                .prefixes(&text[start..])
+                .filter(|&(length, _)| length > 0)
+                .map(|(length, _)| ways[start + length])
+                .sum();
+        }
+
+        ways[0]
+    }
+
+    /// Whether `text` can be written as a sequence of words.
+    pub fn can_segment(&self, text: impl AsRef<[T]>) -> bool {
+        let text = text.as_ref();
+        let mut reachable = vec![false; text.len() + 1];
+        reachable[text.len()] = true;
+
+        for start in (0..text.len()).rev() {
+            reachable[start] = self
+                .prefixes(&text[start..])
+                .any(|(length, _)| length > 0 && reachable[start + length]);
+        }
+
+        reachable[0]
+    }
+}
+
+impl<T: Ord + Copy> Default for Trie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy, W: AsRef<[T]>> FromIterator<W> for Trie<T> {
+    fn from_iter<I: IntoIterator<Item = W>>(words: I) -> Self {
+        let mut trie = Self::new();
+        words.into_iter().for_each(|word| {
+            trie.insert(word);
+        });
+        trie
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An occurrence of a word in a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// The id of the word in the [`Trie`] the matcher was built from.
+    pub word: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A trie with failure links that finds all occurrences of its words in a text,
+/// in time linear in the length of the text plus the number of matches.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<T = u8> {
+    trie: Trie<T>,
+    /// The node of the longest proper suffix of each node that is also in the trie.
+    fail: Vec<usize>,
+    /// The nearest node on the failure chain of each node where a word ends.
+    output: Vec<Option<usize>>,
+}
+
+impl<T: Ord + Copy> AhoCorasick<T> {
+    pub fn new(trie: Trie<T>) -> Self {
+        let mut fail = vec![ROOT; trie.nodes.len()];
+        let mut output = vec![None; trie.nodes.len()];
+        let mut queue: VecDeque<usize> = trie.nodes[ROOT].children.iter().map(|c| c.1).collect();
+
+        // breadth first, so that the links of shorter suffixes are known.
+        while let Some(node) = queue.pop_front() {
+            let suffix = fail[node];
+            output[node] = if trie.nodes[suffix].word.is_some() {
+                Some(suffix)
+            } else {
+                output[suffix]
+            };
+
+            for &(symbol, child) in &trie.nodes[node].children {
+                let mut candidate = suffix;
+                fail[child] = loop {
+                    match trie.nodes[candidate].child(&symbol) {
+                        Some(next) if next != child => break next,
+                        _ if candidate == ROOT => break ROOT,
+                        _ => candidate = fail[candidate],
+                    }
+                };
+                queue.push_back(child);
+            }
+        }
+
+        Self { trie, fail, output }
+    }
+
+    /// The trie the matcher was built from, e.g. to look up word ids.
+    pub fn trie(&self) -> &Trie<T> {
+        &self.trie
+    }
+
+    /// Iterates over all occurrences of the words in `text`, including overlapping ones,
+    /// ordered by their end. Occurrences with the same end are ordered from the longest.
+    pub fn find_all<'a>(&'a self, text: &'a [T]) -> impl Iterator<Item = Match> + 'a {
+        let nodes = &self.trie.nodes;
+        let mut state = ROOT;
+
+        text.iter().enumerate().flat_map(move |(index, symbol)| {
+            state = loop {
+                match nodes[state].child(symbol) {
+                    Some(next) => break next,
+                    None if state == ROOT => break ROOT,
+                    None => state = self.fail[state],
+                }
+            };
+
+            let end = index + 1;
+            let first = nodes[state].word.map(|_| state).or(self.output[state]);
+            std::iter::successors(first, |&node| self.output[node]).map(move |node| Match {
+                word: nodes[node].word.unwrap_or_default(),
+                start: end - nodes[node].depth,
+                end,
+            })
+        })
+    }
+}
+
+impl<T: Ord + Copy, W: AsRef<[T]>> FromIterator<W> for AhoCorasick<T> {
+    fn from_iter<I: IntoIterator<Item = W>>(words: I) -> Self {
+        Self::new(words.into_iter().collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_words() {
+        let mut trie = Trie::new();
+
+        assert_eq!(trie.insert("ab"), 0);
+        assert_eq!(trie.insert("acd"), 1);
+        assert_eq!(trie.insert("ab"), 0);
+        assert_eq!(trie.len(), 2);
+
+        assert!(trie.contains("acd"));
+        assert!(!trie.contains("ac"));
+        assert!(trie.has_prefix("ac"));
+        assert!(!trie.has_prefix("b"));
+        assert_eq!(trie.get("acd"), Some(1));
+
+        let numbers: Trie<u32> = [[1, 2], [1, 3]].into_iter().collect();
+        assert!(numbers.contains([1, 3]) && !numbers.contains([2]));
+    }
+
+    #[test]
+    fn iterates_prefixes() {
+        let trie: Trie = ["r", "rb", "rbg", "b"].into_iter().collect();
+
+        assert_eq!(
+            trie.prefixes(b"rbgx").collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (3, 2)]
+        );
+        assert_eq!(trie.prefixes(b"gr").count(), 0);
+    }
+
+    #[test]
+    fn segments_words() {
+        let towels: Trie = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"]
+            .into_iter()
+            .collect();
+
+        assert_eq!(towels.count_segmentations("brwrr"), 2);
+        assert_eq!(towels.count_segmentations("gbbr"), 4);
+        assert_eq!(towels.count_segmentations("rrbgbr"), 6);
+        assert_eq!(towels.count_segmentations("ubwu"), 0);
+        assert_eq!(towels.count_segmentations(""), 1);
+
+        assert!(towels.can_segment("bwurrg"));
+        assert!(!towels.can_segment("bbrgwb"));
+    }
+
+    #[test]
+    fn finds_all_matches() {
+        let matcher: AhoCorasick = ["he", "she", "his", "hers"].into_iter().collect();
+        let matches: Vec<_> = matcher
+            .find_all(b"ushers")
+            .map(|m| (m.word, m.start, m.end))
+            .collect();
+
+        assert_eq!(matches, [(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+        assert_eq!(matcher.find_all(b"xyz").count(), 0);
+        assert_eq!(matcher.trie().get("his"), Some(2));
+    }
+}
@@ -0,0 +1,395 @@
+//! A small virtual machine for puzzles that define their own instruction set,
+//! like the "3-bit computer" of 2024 day 17.
+use std::{
+    fmt::{Debug, Display, Write},
+    iter,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+};
+
+/// What happens after an instruction was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect<O> {
+    /// Continue with the following instruction.
+    Next,
+    /// Continue at the given address.
+    Jump(usize),
+    /// Emit a value and continue with the following instruction.
+    Output(O),
+    /// Stop the machine.
+    Halt,
+}
+
+/// An instruction set: how instructions are decoded from a program and what they do.
+pub trait InstructionSet {
+    /// A single word of the program, e.g. a 3-bit number.
+    type Word: Copy;
+    type Registers: Clone + Debug;
+    /// A decoded instruction. It displays as a line of the [`disassemble`] listing.
+    type Instruction: Copy + Display;
+    type Output: Copy;
+
+    /// Decodes the instruction at `address`, returning it with the number of words it spans.
+    /// Returns `None` if there is no instruction at `address`, which halts the machine.
+    fn decode(program: &[Self::Word], address: usize) -> Option<(Self::Instruction, usize)>;
+
+    fn execute(
+        instruction: Self::Instruction,
+        registers: &mut Self::Registers,
+    ) -> Effect<Self::Output>;
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A register name that indexes [`Registers`], usually a fieldless enum.
+pub trait Register: Copy + Debug + 'static {
+    /// All registers, ordered by their index.
+    const ALL: &'static [Self];
+
+    fn index(self) -> usize;
+}
+
+/// A register file that is indexed by register names instead of numbers or strings,
+/// e.g. `registers[Register::A]`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Registers<R, W, const N: usize> {
+    values: [W; N],
+    names: PhantomData<R>,
+}
+
+impl<R: Register, W: Copy, const N: usize> Registers<R, W, N> {
+    pub fn new(values: [W; N]) -> Self {
+        Self {
+            values,
+            names: PhantomData,
+        }
+    }
+}
+
+impl<R: Register, W: Copy + Default, const N: usize> Default for Registers<R, W, N> {
+    fn default() -> Self {
+        Self::new([W::default(); N])
+    }
+}
+
+impl<R: Register, W, const N: usize> Index<R> for Registers<R, W, N> {
+    type Output = W;
+
+    fn index(&self, register: R) -> &Self::Output {
+        &self.values[register.index()]
+    }
+}
+
+impl<R: Register, W, const N: usize> IndexMut<R> for Registers<R, W, N> {
+    fn index_mut(&mut self, register: R) -> &mut Self::Output {
+        &mut self.values[register.index()]
+    }
+}
+
+impl<R: Register, W: Debug, const N: usize> Debug for Registers<R, W, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(R::ALL.iter().map(|&r| (r, &self.values[r.index()])))
+            .finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An executed instruction, as yielded by [`Vm::steps`] for tracing.
+pub struct Step<I: InstructionSet> {
+    pub address: usize,
+    pub instruction: I::Instruction,
+    pub effect: Effect<I::Output>,
+    /// The registers after the instruction was executed.
+    pub registers: I::Registers,
+}
+
+impl<I: InstructionSet> Display for Step<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instruction = self.instruction.to_string();
+        write!(
+            f,
+            "{:>4}: {instruction:<28} {:?}",
+            self.address, self.registers
+        )
+    }
+}
+
+/// A machine that runs a program of the instruction set `I`.
+pub struct Vm<'a, I: InstructionSet> {
+    program: &'a [I::Word],
+    pub registers: I::Registers,
+    /// Address of the next instruction.
+    pub ip: usize,
+    /// Everything the program emitted so far.
+    pub output: Vec<I::Output>,
+    halted: bool,
+}
+
+impl<'a, I: InstructionSet> Vm<'a, I> {
+    pub fn new(program: &'a [I::Word], registers: I::Registers) -> Self {
+        Self {
+            program,
+            registers,
+            ip: 0,
+            output: Vec::new(),
+            halted: false,
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Executes the next instruction, returning `None` if the machine halted.
+    fn execute_next(&mut self) -> Option<(usize, I::Instruction, Effect<I::Output>)> {
+        if self.halted {
+            return None;
+        }
+
+        let address = self.ip;
+        let Some((instruction, size)) = I::decode(self.program, address) else {
+            self.halted = true;
+            return None;
+        };
+
+        let effect = I::execute(instruction, &mut self.registers);
+        match effect {
+            Effect::Next => self.ip += size,
+            Effect::Jump(target) => self.ip = target,
+            Effect::Output(value) => {
+                self.output.push(value);
+                self.ip += size;
+            }
+            Effect::Halt => self.halted = true,
+        }
+
+        Some((address, instruction, effect))
+    }
+
+    /// Executes a single instruction, returning `None` if the machine halted.
+    pub fn step(&mut self) -> Option<Step<I>> {
+        let (address, instruction, effect) = self.execute_next()?;
+        Some(Step {
+            address,
+            instruction,
+            effect,
+            registers: self.registers.clone(),
+        })
+    }
+
+    /// Runs the program until it halts and returns its output.
+    pub fn run(&mut self) -> &[I::Output] {
+        while self.execute_next().is_some() {}
+        &self.output
+    }
+
+    /// Runs the program until it emits the next value. Returns `None` if it halts before.
+    pub fn next_output(&mut self) -> Option<I::Output> {
+        loop {
+            if let (_, _, Effect::Output(value)) = self.execute_next()? {
+                return Some(value);
+            }
+        }
+    }
+
+    /// Iterates over the executed instructions, e.g. to print a trace of the program.
+    pub fn steps(&mut self) -> impl Iterator<Item = Step<I>> + use<'_, 'a, I> {
+        iter::from_fn(|| self.step())
+    }
+}
+
+/// Returns a listing with one decoded instruction per line, prefixed by its address.
+/// Decoding continues after the last instruction, so that data that follows is shown as well.
+pub fn disassemble<I: InstructionSet>(program: &[I::Word]) -> String {
+    let mut listing = String::new();
+    let mut address = 0;
+
+    while address < program.len() {
+        match I::decode(program, address) {
+            Some((instruction, size)) => {
+                let _ = writeln!(listing, "{address:>4}: {instruction}");
+                address += size.max(1);
+            }
+            None => {
+                let _ = writeln!(listing, "{address:>4}: ???");
+                address += 1;
+            }
+        }
+    }
+
+    listing
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Searches the smallest input for which `run` outputs exactly `target`,
+/// e.g. the value of a register that makes a program output itself.
+///
+/// This assumes a program that loops over its input, emitting one value per iteration and
+/// shifting `bits` bits out of the input, until the input is zero. The output then only depends
+/// on the upper bits of the input, so the input is built up from its most significant bits
+/// while matching the output from its end.
+pub fn search_backwards<O: PartialEq>(
+    target: &[O],
+    bits: u32,
+    mut run: impl FnMut(u64) -> Vec<O>,
+) -> Option<u64> {
+    fn search<O: PartialEq>(
+        target: &[O],
+        bits: u32,
+        run: &mut impl FnMut(u64) -> Vec<O>,
+        prefix: u64,
+        matched: usize,
+    ) -> Option<u64> {
+        if matched == target.len() {
+            return Some(prefix);
+        }
+
+        let suffix = &target[target.len() - matched - 1..];
+        (0..1 << bits).find_map(|chunk| {
+            let candidate = prefix.checked_mul(1 << bits)? | chunk;
+            if run(candidate) == suffix {
+                search(target, bits, run, candidate, matched + 1)
+            } else {
+                None
+            }
+        })
+    }
+
+    search(target, bits, &mut run, 0, 0)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Reg {
+        X,
+        Y,
+    }
+
+    impl Register for Reg {
+        const ALL: &'static [Self] = &[Reg::X, Reg::Y];
+
+        fn index(self) -> usize {
+            self as usize
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum Instruction {
+        Dec(Reg),
+        Inc(Reg),
+        Jnz(Reg, usize),
+        Out(Reg),
+    }
+
+    impl Display for Instruction {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Instruction::Dec(r) => write!(f, "dec {r:?}"),
+                Instruction::Inc(r) => write!(f, "inc {r:?}"),
+                Instruction::Jnz(r, target) => write!(f, "jnz {r:?} {target}"),
+                Instruction::Out(r) => write!(f, "out {r:?}"),
+            }
+        }
+    }
+
+    /// Toy instruction set: opcode and register index, followed by a target for jumps.
+    struct Toy;
+
+    impl InstructionSet for Toy {
+        type Word = u8;
+        type Registers = Registers<Reg, u8, 2>;
+        type Instruction = Instruction;
+        type Output = u8;
+
+        fn decode(program: &[u8], address: usize) -> Option<(Instruction, usize)> {
+            let register = Reg::ALL[usize::from(*program.get(address + 1)?)];
+            match program[address] {
+                0 => Some((Instruction::Dec(register), 2)),
+                1 => Some((Instruction::Inc(register), 2)),
+                2 => {
+                    let target = usize::from(*program.get(address + 2)?);
+                    Some((Instruction::Jnz(register, target), 3))
+                }
+                3 => Some((Instruction::Out(register), 2)),
+                _ => None,
+            }
+        }
+
+        fn execute(instruction: Instruction, registers: &mut Self::Registers) -> Effect<u8> {
+            match instruction {
+                Instruction::Dec(r) => registers[r] -= 1,
+                Instruction::Inc(r) => registers[r] += 1,
+                Instruction::Jnz(r, target) if registers[r] != 0 => return Effect::Jump(target),
+                Instruction::Jnz(..) => {}
+                Instruction::Out(r) => return Effect::Output(registers[r]),
+            }
+            Effect::Next
+        }
+    }
+
+    /// Counts `Y` up while counting `X` down to zero, printing `Y` on each iteration.
+    const PROGRAM: [u8; 9] = [1, 1, 3, 1, 0, 0, 2, 0, 0];
+
+    #[test]
+    fn runs_programs() {
+        let mut vm = Vm::<Toy>::new(&PROGRAM, Registers::new([3, 0]));
+
+        assert_eq!(vm.run(), [1, 2, 3]);
+        assert!(vm.is_halted());
+        assert_eq!(vm.registers[Reg::X], 0);
+        assert_eq!(vm.registers[Reg::Y], 3);
+        assert_eq!(format!("{:?}", vm.registers), "{X: 0, Y: 3}");
+
+        let mut vm = Vm::<Toy>::new(&PROGRAM, Registers::new([2, 0]));
+        assert_eq!(vm.next_output(), Some(1));
+        assert_eq!(vm.next_output(), Some(2));
+        assert_eq!(vm.next_output(), None);
+    }
+
+    #[test]
+    fn traces_steps() {
+        let mut vm = Vm::<Toy>::new(&PROGRAM, Registers::new([1, 0]));
+        let trace: Vec<String> = vm.steps().map(|step| step.to_string()).collect();
+
+        assert_eq!(trace.len(), 4);
+        assert_eq!(
+            trace[1].split_whitespace().collect::<Vec<_>>(),
+            ["2:", "out", "Y", "{X:", "1,", "Y:", "1}"]
+        );
+        assert_eq!(vm.output, [1]);
+    }
+
+    #[test]
+    fn disassembles_programs() {
+        assert_eq!(
+            disassemble::<Toy>(&[1, 1, 3, 1, 0, 0, 2, 0, 0, 9]),
+            "   0: inc Y\n   2: out Y\n   4: dec X\n   6: jnz X 0\n   9: ???\n"
+        );
+    }
+
+    #[test]
+    fn searches_backwards() {
+        // emits the octal digits of the input, starting with the lowest.
+        let digits = |mut value: u64| {
+            let mut output = vec![value % 8];
+            value /= 8;
+            while value != 0 {
+                output.push(value % 8);
+                value /= 8;
+            }
+            output
+        };
+
+        assert_eq!(search_backwards(&[1, 2, 3], 3, digits), Some(0o321));
+        assert_eq!(search_backwards(&[0], 3, digits), Some(0));
+        assert_eq!(search_backwards(&[1, 0], 3, digits), None);
+    }
+}